@@ -1,9 +1,12 @@
-pub fn ch1(){
+use std::io::{self, Write};
+
+pub fn ch1(out: &mut dyn Write) -> io::Result<()> {
        //there is funtion main
        // and it is the entry point of the program
        //it is ususlly in src/main.rs
 
-       println!("Hello, world!");
+       writeln!(out, "Hello, world!")?;
        //is annotation "!" is macro , it is different from function
        //in ch1, new deal about using cargo framework for rust, it is very common to use 
+       Ok(())
 }
\ No newline at end of file
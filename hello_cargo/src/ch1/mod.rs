@@ -1 +1,29 @@
-pub mod ch1;
\ No newline at end of file
+#![allow(clippy::module_inception)]
+
+pub mod ch1;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch1;
+
+impl Chapter for Ch1 {
+    fn id(&self) -> &'static str {
+        "ch1"
+    }
+
+    fn title(&self) -> &'static str {
+        "Getting Started"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["hello world", "cargo", "macros"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch1::ch1(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch1];
@@ -1,6 +1,8 @@
 // Chapter 10.1: Generic Data Types - Notes & Examples
 
-pub fn ch10_1() {
+use std::io::{self, Write};
+
+pub fn ch10_1(out: &mut dyn Write) -> io::Result<()> {
     // 1. What are Generics?
     // ---------------------
     // - Generics allow definition of functions, structs, enums, and methods that work with many concrete types.
@@ -9,6 +11,7 @@ pub fn ch10_1() {
     // 2. Generics in Functions
     // ------------------------
     // Example: Largest element in a slice (with trait bound)
+    #[allow(dead_code)] // shown, not called
     fn largest<T: PartialOrd>(list: &[T]) -> &T {
         let mut largest = &list[0];
         for item in list {
@@ -21,28 +24,37 @@ pub fn ch10_1() {
 
     // 3. Generics in Structs
     // ----------------------
+    #[allow(dead_code)] // the fields only show the type parameter
     struct Point<T> {
         x: T,
         y: T,
     }
+    #[allow(unused_variables)] // a Point<i32>
     let integer = Point { x: 5, y: 10 };
+    #[allow(unused_variables)] // a Point<f64>
     let float = Point { x: 1.0, y: 4.0 };
 
     // Using different types for x and y:
+    #[allow(dead_code)] // as for `Point`
     struct Point2<T, U> {
         x: T,
         y: U,
     }
+    #[allow(unused_variables)] // x and y of one type
     let both_integer = Point2 { x: 5, y: 10 };
+    #[allow(unused_variables)] // x and y of one type
     let both_float = Point2 { x: 1.0, y: 4.0 };
+    #[allow(unused_variables)] // x and y of different types
     let integer_and_float = Point2 { x: 5, y: 4.0 };
 
     // 4. Generics in Enums
     // --------------------
+    #[allow(dead_code)] // mirrors std::option::Option
     enum Option<T> {
         Some(T),
         None,
     }
+    #[allow(dead_code)] // mirrors std::result::Result
     enum Result<T, E> {
         Ok(T),
         Err(E),
@@ -50,16 +62,19 @@ pub fn ch10_1() {
 
     // 5. Generics in Methods
     // ----------------------
+    #[allow(dead_code)] // its methods are the example
     struct Point3<T> {
         x: T,
         y: T,
     }
     impl<T> Point3<T> {
+        #[allow(dead_code)] // a method on every Point3<T>
         fn x(&self) -> &T {
             &self.x
         }
     }
     impl Point3<f32> {
+        #[allow(dead_code)] // only for Point3<f32>
         fn distance_from_origin(&self) -> f32 {
             (self.x.powi(2) + self.y.powi(2)).sqrt()
         }
@@ -80,6 +95,7 @@ pub fn ch10_1() {
     }
     let p1 = Point4 { x: 5, y: 10.4 };
     let p2 = Point4 { x: "Hello", y: 'c' };
+    #[allow(unused_variables)] // the types of the mixed-up fields are the point
     let p3 = p1.mixup(p2); // p3.x = 5, p3.y = 'c'
 
     // 6. Generics & Performance (Monomorphization)
@@ -87,9 +103,12 @@ pub fn ch10_1() {
     // - At compile time, Rust generates specialized code for each concrete type used with generics.
     // - No runtime cost for using generics.
     // Example:
+    #[allow(unused_variables)] // monomorphized to Option<i32>
     let integer = Some(5);   // Option<i32>
+    #[allow(unused_variables)] // monomorphized to Option<f64>
     let float = Some(5.0);   // Option<f64>
     // Rust generates Option_i32 and Option_f64 behind the scenes.
 
-    println!("See source for notes and examples on generic data types in Rust.");
+    writeln!(out, "See source for notes and examples on generic data types in Rust.")?;
+    Ok(())
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 // Chapter 10.2: Traits - Defining Shared Behavior - Notes & Examples
 
-pub fn ch10_2() {
+use std::io::{self, Write};
+
+pub fn ch10_2(out: &mut dyn Write) -> io::Result<()> {
     // 1. What is a Trait?
     // -------------------
     // - Traits define shared behavior: a set of method signatures that types can implement.
@@ -8,38 +10,15 @@ pub fn ch10_2() {
 
     // 2. Defining a Trait
     // -------------------
-    pub trait Summary {
-        fn summarize(&self) -> String;
-    }
+    // (`Summary` is defined below the function)
 
     // 3. Implementing a Trait for Types
     // ---------------------------------
-    pub struct NewsArticle {
-        pub headline: String,
-        pub location: String,
-        pub author: String,
-        pub content: String,
-    }
-    impl Summary for NewsArticle {
-        fn summarize(&self) -> String {
-            format!("{}, by {} ({})", self.headline, self.author, self.location)
-        }
-    }
-
-    pub struct SocialPost {
-        pub username: String,
-        pub content: String,
-        pub reply: bool,
-        pub repost: bool,
-    }
-    impl Summary for SocialPost {
-        fn summarize(&self) -> String {
-            format!("{}: {}", self.username, self.content)
-        }
-    }
+    // (`NewsArticle` and `SocialPost` below implement `Summary`)
 
     // 4. Default Implementations in Traits
     // ------------------------------------
+    #[allow(dead_code)] // nothing implements it in the demo
     pub trait SummaryDefault {
         fn summarize(&self) -> String {
             String::from("(Read more...)")
@@ -49,13 +28,11 @@ pub fn ch10_2() {
 
     // 5. Traits as Parameters & Trait Bounds
     // --------------------------------------
-    // (a) Using impl Trait syntax:
-    pub fn notify(item: &impl Summary) {
-        println!("Breaking news! {}", item.summarize());
-    }
+    // (a) Using impl Trait syntax: see `notify` below the function.
     // (b) Using explicit trait bounds (generic):
-    pub fn notify_bound<T: Summary>(item: &T) {
-        println!("Breaking news! {}", item.summarize());
+    #[allow(dead_code)] // the trait-bound spelling of `notify`
+    pub fn notify_bound<T: Summary>(out: &mut dyn Write, item: &T) -> io::Result<()> {
+        writeln!(out, "Breaking news! {}", item.summarize())
     }
     // (c) Multiple trait bounds:
     // pub fn notify<T: Summary + Display>(item: &T) { ... }
@@ -70,6 +47,7 @@ pub fn ch10_2() {
 
     // 7. Returning Types that Implement Traits
     // ---------------------------------------
+    #[allow(dead_code)] // only the signature matters
     fn returns_summarizable() -> impl Summary {
         SocialPost {
             username: String::from("horse_ebooks"),
@@ -83,21 +61,24 @@ pub fn ch10_2() {
     // 8. Conditionally Implementing Methods with Trait Bounds
     // -------------------------------------------------------
     use std::fmt::Display;
+    #[allow(dead_code)] // its methods are the example
     struct Pair<T> {
         x: T,
         y: T,
     }
     impl<T> Pair<T> {
+        #[allow(dead_code)] // available for every T
         fn new(x: T, y: T) -> Self {
             Self { x, y }
         }
     }
     impl<T: Display + PartialOrd> Pair<T> {
-        fn cmp_display(&self) {
+        #[allow(dead_code)] // only when T is Display + PartialOrd
+        fn cmp_display(&self, out: &mut dyn Write) -> io::Result<()> {
             if self.x >= self.y {
-                println!("The largest member is x = {}", self.x);
+                writeln!(out, "The largest member is x = {}", self.x)
             } else {
-                println!("The largest member is y = {}", self.y);
+                writeln!(out, "The largest member is y = {}", self.y)
             }
         }
     }
@@ -105,11 +86,47 @@ pub fn ch10_2() {
     // 9. Blanket Implementations
     // -------------------------
     // Standard library: impl<T: Display> ToString for T
+    #[allow(unused_variables)] // the blanket ToString impl at work
     let s = 3.to_string(); // works because i32 implements Display
 
     // 10. Orphan Rule and Coherence
     // -----------------------------
     // - You can implement a trait for a type only if either the trait or the type is local to your crate.
 
-    println!("See source for notes and examples on traits and shared behavior in Rust.");
-}
\ No newline at end of file
+    writeln!(out, "See source for notes and examples on traits and shared behavior in Rust.")?;
+    Ok(())
+}
+
+/// Shared behavior for anything that can be summarized in one line.
+pub trait Summary {
+    fn summarize(&self) -> String;
+}
+
+pub struct NewsArticle {
+    pub headline: String,
+    pub location: String,
+    pub author: String,
+    pub content: String,
+}
+impl Summary for NewsArticle {
+    fn summarize(&self) -> String {
+        format!("{}, by {} ({})", self.headline, self.author, self.location)
+    }
+}
+
+pub struct SocialPost {
+    pub username: String,
+    pub content: String,
+    pub reply: bool,
+    pub repost: bool,
+}
+impl Summary for SocialPost {
+    fn summarize(&self) -> String {
+        format!("{}: {}", self.username, self.content)
+    }
+}
+
+/// Writes a "Breaking news!" line for any `Summary` (the `impl Trait` parameter form).
+pub fn notify(out: &mut dyn Write, item: &impl Summary) -> io::Result<()> {
+    writeln!(out, "Breaking news! {}", item.summarize())
+}
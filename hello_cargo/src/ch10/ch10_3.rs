@@ -1,6 +1,8 @@
 // Chapter 10.3: Validating References with Lifetimes - Notes & Examples
 
-pub fn ch10_3() {
+use std::io::{self, Write};
+
+pub fn ch10_3(out: &mut dyn Write) -> io::Result<()> {
     // 1. What are Lifetimes?
     // ----------------------
     // - Lifetimes are generics that ensure references are valid as long as needed.
@@ -22,6 +24,7 @@ pub fn ch10_3() {
     // 4. Lifetime Annotations in Functions
     // ------------------------------------
     // Function returning the longer of two string slices requires explicit lifetimes:
+    #[allow(dead_code)] // the signature is the lesson
     fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
         if x.len() > y.len() { x } else { y }
     }
@@ -29,6 +32,7 @@ pub fn ch10_3() {
 
     // 5. Lifetime Annotations in Structs
     // ----------------------------------
+    #[allow(dead_code)] // only shows a lifetime on a struct
     struct ImportantExcerpt<'a> {
         part: &'a str,
     }
@@ -41,32 +45,40 @@ pub fn ch10_3() {
 
     // 7. Lifetime in Methods
     // ----------------------
+    #[allow(dead_code)] // the elision rules applied to methods
     impl<'a> ImportantExcerpt<'a> {
         fn level(&self) -> i32 { 3 }
-        fn announce_and_return_part(&self, announcement: &str) -> &str {
-            println!("Attention please: {announcement}");
-            self.part
+        fn announce_and_return_part(
+            &self,
+            out: &mut dyn Write,
+            announcement: &str,
+        ) -> io::Result<&str> {
+            writeln!(out, "Attention please: {announcement}")?;
+            Ok(self.part)
         }
     }
 
     // 8. The 'static Lifetime
     // -----------------------
+    #[allow(unused_variables)] // every literal is &'static str
     let s: &'static str = "I have a static lifetime.";
     // All string literals are 'static (live for the duration of the program).
 
     // 9. Combining Lifetimes, Traits, and Generics
     // --------------------------------------------
     use std::fmt::Display;
+    #[allow(dead_code)] // lifetimes, generics and bounds together
     fn longest_with_an_announcement<'a, T>(
+        out: &mut dyn Write,
         x: &'a str,
         y: &'a str,
         ann: T,
-    ) -> &'a str
+    ) -> io::Result<&'a str>
     where
         T: Display,
     {
-        println!("Announcement! {ann}");
-        if x.len() > y.len() { x } else { y }
+        writeln!(out, "Announcement! {ann}")?;
+        Ok(if x.len() > y.len() { x } else { y })
     }
 
     // 10. Summary
@@ -76,5 +88,6 @@ pub fn ch10_3() {
     // - Most of the time, the compiler infers lifetimes. Annotate when ambiguous.
     // - Lifetimes, generics, and traits let you write flexible, safe, reusable code.
 
-    println!("See source for notes and examples on validating references with lifetimes in Rust.");
+    writeln!(out, "See source for notes and examples on validating references with lifetimes in Rust.")?;
+    Ok(())
 }
\ No newline at end of file
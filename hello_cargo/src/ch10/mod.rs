@@ -2,3 +2,68 @@ pub mod ch10_1;
 pub mod ch10_2;
 pub mod ch10_3;
 
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch10_1;
+
+impl Chapter for Ch10_1 {
+    fn id(&self) -> &'static str {
+        "ch10_1"
+    }
+
+    fn title(&self) -> &'static str {
+        "Generic Data Types"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["generics", "structs", "enums", "methods", "monomorphization"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch10_1::ch10_1(out)
+    }
+}
+
+pub struct Ch10_2;
+
+impl Chapter for Ch10_2 {
+    fn id(&self) -> &'static str {
+        "ch10_2"
+    }
+
+    fn title(&self) -> &'static str {
+        "Traits: Defining Shared Behavior"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["traits", "default implementations", "trait bounds", "impl Trait", "where clauses"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch10_2::ch10_2(out)
+    }
+}
+
+pub struct Ch10_3;
+
+impl Chapter for Ch10_3 {
+    fn id(&self) -> &'static str {
+        "ch10_3"
+    }
+
+    fn title(&self) -> &'static str {
+        "Validating References with Lifetimes"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["lifetimes", "borrow checker", "lifetime elision", "static"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch10_3::ch10_3(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch10_1, &Ch10_2, &Ch10_3];
@@ -3,38 +3,40 @@
 // This file contains summary notes and example code for Rust testing concepts.
 // Each section is commented with explanations and example code.
 
-/// ch11: Summary and Examples of Rust Testing Features
-///
-/// 1. What is a Test in Rust?
-///    - Test functions verify your code behaves as expected.
-///    - Mark tests with `#[test]`, run them with `cargo test`.
-///
-/// 2. Anatomy of a Test Function
-///    - Use `#[test]` above the function.
-///    - Typical actions: setup, run code, assert result.
-///
-/// 3. Test Module Structure
-///    - Use `#[cfg(test)]` to only compile test code for `cargo test`.
-///    - Define tests in a `mod tests` block, use `use super::*;` for access.
-///
-/// 4. Assertions
-///    - `assert!(cond)`: Condition is true.
-///    - `assert_eq!(a, b)`: a == b.
-///    - `assert_ne!(a, b)`: a != b.
-///    - Add custom messages for failures.
-///
-/// 5. Panics in Tests
-///    - Use `#[should_panic]` to check for panics.
-///    - Use `#[should_panic(expected = "...")]` to match panic messages.
-///
-/// 6. Result<T, E> in Tests
-///    - Test can return `Result<(), String>`.
-///    - Use `?` for error propagation.
-///    - Return `Ok(())` to pass, `Err(...)` to fail.
-///
-/// 7. Practical Tips
-///    - Group tests, use custom messages, filter with `cargo test`.
-///    - Tests run in parallel, each in its own thread.
+use std::io::{self, Write};
+
+// ch11: Summary and Examples of Rust Testing Features
+//
+// 1. What is a Test in Rust?
+//    - Test functions verify your code behaves as expected.
+//    - Mark tests with `#[test]`, run them with `cargo test`.
+//
+// 2. Anatomy of a Test Function
+//    - Use `#[test]` above the function.
+//    - Typical actions: setup, run code, assert result.
+//
+// 3. Test Module Structure
+//    - Use `#[cfg(test)]` to only compile test code for `cargo test`.
+//    - Define tests in a `mod tests` block, use `use super::*;` for access.
+//
+// 4. Assertions
+//    - `assert!(cond)`: Condition is true.
+//    - `assert_eq!(a, b)`: a == b.
+//    - `assert_ne!(a, b)`: a != b.
+//    - Add custom messages for failures.
+//
+// 5. Panics in Tests
+//    - Use `#[should_panic]` to check for panics.
+//    - Use `#[should_panic(expected = "...")]` to match panic messages.
+//
+// 6. Result<T, E> in Tests
+//    - Test can return `Result<(), String>`.
+//    - Use `?` for error propagation.
+//    - Return `Ok(())` to pass, `Err(...)` to fail.
+//
+// 7. Practical Tips
+//    - Group tests, use custom messages, filter with `cargo test`.
+//    - Tests run in parallel, each in its own thread.
 
 /// Example 1: A simple function to test
 pub fn add(left: u64, right: u64) -> u64 {
@@ -60,6 +62,7 @@ pub struct Guess {
 }
 
 impl Guess {
+    #[allow(clippy::manual_range_contains)] // as in the book's listing 11-8
     pub fn new(value: i32) -> Guess {
         if value < 1 || value > 100 {
             panic!("Guess value must be between 1 and 100, got {value}.");
@@ -73,6 +76,19 @@ pub fn add_two(a: usize) -> usize {
     a + 2
 }
 
+/// Runs the examples that the tests below check.
+pub fn ch11_1(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "add(2, 2) = {}", add(2, 2))?;
+
+    let larger = Rectangle { width: 8, height: 7 };
+    let smaller = Rectangle { width: 5, height: 1 };
+    writeln!(out, "{:?} can hold {:?}: {}", larger, smaller, larger.can_hold(&smaller))?;
+
+    writeln!(out, "Guess::new(50).value = {}", Guess::new(50).value)?;
+    writeln!(out, "add_two(2) = {}", add_two(2))?;
+    writeln!(out, "Run `cargo test ch11` to see these examples tested.")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -3,7 +3,16 @@
 // This function contains commented notes and code examples
 // to help understand test control in Rust (parallelism, output, filtering, ignoring).
 
-pub fn ch11_2() {
+use std::io::{self, Write};
+
+// Prints to stdout on purpose: section 3 is about how the test harness
+// captures that output.
+pub fn prints_and_returns_10(a: i32) -> i32 {
+    println!("I got the value {a}");
+    10
+}
+
+pub fn ch11_2(out: &mut dyn Write) -> io::Result<()> {
     // 1. Running tests in parallel (default behavior)
     // - Rust runs tests in parallel using threads for speed.
     // - Don't let tests interfere via shared state (files, env vars, etc).
@@ -22,28 +31,8 @@ pub fn ch11_2() {
     // - To always show output, use:
     // $ cargo test -- --show-output
 
-    // Example function and tests:
-    fn prints_and_returns_10(a: i32) -> i32 {
-        println!("I got the value {a}");
-        10
-    }
-
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-
-        #[test]
-        fn this_test_will_pass() {
-            let value = prints_and_returns_10(4);
-            assert_eq!(value, 10); // Output is hidden unless --show-output is used
-        }
-
-        #[test]
-        fn this_test_will_fail() {
-            let value = prints_and_returns_10(8);
-            assert_eq!(value, 5); // Output will be shown because test fails
-        }
-    }
+    // Example function and tests: prints_and_returns_10 and the tests module
+    // at the bottom of this file.
 
     // 4. Running a subset of tests by name
     // - You can run only tests whose name matches a string:
@@ -73,4 +62,25 @@ pub fn ch11_2() {
     // | Run all tests, including ignored ones  | cargo test -- --include-ignored             |
 
     // Note: All flags after '--' are passed to the test binary, not to cargo itself.
-}
\ No newline at end of file
+
+    writeln!(out, "See source for notes on running tests in parallel, capturing output and filtering.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn this_test_will_pass() {
+        let value = prints_and_returns_10(4);
+        assert_eq!(value, 10); // Output is hidden unless --show-output is used
+    }
+
+    // Run with `cargo test -- --ignored` to see the captured output.
+    #[test]
+    #[ignore = "fails on purpose to demonstrate captured output"]
+    fn this_test_will_fail() {
+        let value = prints_and_returns_10(8);
+        assert_eq!(value, 5); // Output will be shown because test fails
+    }
+}
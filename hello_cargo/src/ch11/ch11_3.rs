@@ -2,7 +2,9 @@
 // -------------------------------------
 // Summary notes and code snippets for understanding Unit and Integration Tests.
 
-pub fn ch11_3() {
+use std::io::{self, Write};
+
+pub fn ch11_3(out: &mut dyn Write) -> io::Result<()> {
     /*
     ----------------------------------------------------------------------
     Test Organization in Rust: Unit Tests vs Integration Tests
@@ -114,4 +116,6 @@ pub fn ch11_3() {
        - For binary crates: put logic in lib.rs for testability.
 
     */
-}
\ No newline at end of file
+
+    writeln!(out, "See source for notes on organizing unit and integration tests.")
+}
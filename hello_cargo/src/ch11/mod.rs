@@ -1 +1,69 @@
-pub mod ch11;
\ No newline at end of file
+pub mod ch11_1;
+pub mod ch11_2;
+pub mod ch11_3;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch11_1;
+
+impl Chapter for Ch11_1 {
+    fn id(&self) -> &'static str {
+        "ch11_1"
+    }
+
+    fn title(&self) -> &'static str {
+        "How to Write Tests"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["tests", "assert!", "assert_eq!", "should_panic", "Result in tests"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch11_1::ch11_1(out)
+    }
+}
+
+pub struct Ch11_2;
+
+impl Chapter for Ch11_2 {
+    fn id(&self) -> &'static str {
+        "ch11_2"
+    }
+
+    fn title(&self) -> &'static str {
+        "Controlling How Tests Are Run"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["cargo test", "test threads", "captured output", "ignore", "filtering"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch11_2::ch11_2(out)
+    }
+}
+
+pub struct Ch11_3;
+
+impl Chapter for Ch11_3 {
+    fn id(&self) -> &'static str {
+        "ch11_3"
+    }
+
+    fn title(&self) -> &'static str {
+        "Test Organization"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["unit tests", "integration tests", "cfg(test)", "tests directory"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch11_3::ch11_3(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch11_1, &Ch11_2, &Ch11_3];
@@ -1,35 +1,1079 @@
 use std::env;
-use std::fs;
+use std::error::Error;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use std::process;
+use std::time::Instant;
 
-pub fn ch12() {
-    let args: Vec<String> = env::args().collect();
+pub use super::glob::Glob;
+use super::json::{self, JsonPrinter};
+pub use super::matcher::{Matcher, Mode};
+use super::output::{self, CountPrinter, FileListPrinter, LinePrinter, Printer, RankedPrinter};
+pub use super::output::{ColorChoice, Format, Summary};
+use super::parallel::MapError;
+pub use super::settings::{Defaults, Source};
+pub use super::walk::WalkOptions;
+use super::{index, parallel, replace, walk};
+use crate::cli;
+pub use crate::cli::{ArgsError, Opt};
 
-    let config = Config::new(&args).unwrap_or_else(|err| {
-        println!("Problem parsing arguments: {err}");
-        process::exit(1);
-    });
+/// The poem shipped next to this file, used by the chapter demo.
+pub const POEM: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/ch12/poem.txt");
 
-    let contents = fs::read_to_string(config.file).unwrap_or_else(|err| {
-        println!("Problem reading file: {err}");
-        process::exit(1);
-    });
+/// Entry point for `hello_cargo minigrep [options] <query> <path>...`; `args[0]`
+/// is the command name.
+///
+/// Exits like grep: 0 when a line matched, 1 when nothing matched and 2 on errors.
+/// `--help` and `--version` print to stdout and exit with 0. The subcommands
+/// `index` and `query` build and search an index instead, see `index`.
+pub fn ch12(args: &[String]) {
+    match args.get(1).map(String::as_str) {
+        Some("index") => return index::index_main(&args[1..]),
+        Some("query") => return index::query_main(&args[1..]),
+        _ => {}
+    }
+    let config = Config::new(args).unwrap_or_else(|err| cli::exit_with(err, usage, "minigrep"));
+    if config.print_config {
+        if let Err(e) = config.write_settings(&mut io::stdout()) {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+        return;
+    }
+    let mut config = config;
+    config.color = config.color.for_stdout();
+
+    match run(config, &mut io::stdout()) {
+        Ok(true) => process::exit(0),
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+    }
+}
+
+/// The options `Config::new` understands, in the order `--help` lists them.
+#[rustfmt::skip]
+pub const OPTIONS: &[Opt] = &[
+    Opt::new(Some('i'), "ignore-case", "ignore case (the default when IGNORE_CASE is set)"),
+    Opt::new(Some('s'), "case-sensitive", "match case even when IGNORE_CASE is set"),
+    Opt::new(Some('F'), "fixed-strings", "match the query as a plain substring (the default)"),
+    Opt::new(Some('w'), "word-regexp", "match the query only as a whole word"),
+    Opt::new(Some('E'), "regexp", "match the query as a regular expression"),
+    Opt::new(None, "fuzzy", "match the query's characters in order, best matches first"),
+    Opt::new(Some('v'), "invert-match", "select the lines that don't match"),
+    Opt::new(Some('m'), "max-count", "stop each file after n selected lines").value("n"),
+    Opt::new(Some('A'), "after-context", "print n lines after each match").value("n"),
+    Opt::new(Some('B'), "before-context", "print n lines before each match").value("n"),
+    Opt::new(Some('C'), "context", "print n lines around each match").value("n"),
+    Opt::new(None, "color", "color the output: always, never or auto")
+        .value("when")
+        .alias("colour"),
+    Opt::new(Some('c'), "count", "print the number of selected lines per file"),
+    Opt::new(Some('l'), "files-with-matches", "print only the names of files with a match"),
+    Opt::new(Some('L'), "files-without-match", "print only the names of files without one"),
+    Opt::new(None, "json", "print matches as JSON records"),
+    Opt::new(Some('r'), "replace", "print lines with each match replaced ($1 is a capture group)").value("text"),
+    Opt::new(None, "in-place", "with --replace, rewrite the files instead of printing"),
+    Opt::new(None, "dry-run", "with --replace, print the changes as a diff without making them"),
+    Opt::new(None, "include", "search only files matching the glob (repeatable)").value("glob"),
+    Opt::new(None, "exclude", "skip files and directories matching the glob").value("glob"),
+    Opt::new(None, "no-ignore", "don't honor .gitignore files"),
+    Opt::new(Some('j'), "jobs", "search up to n files at once").value("n"),
+    Opt::new(None, "print-config", "print the settings in effect and where each came from"),
+    Opt::new(Some('h'), "help", "print this help and exit"),
+    Opt::new(Some('V'), "version", "print the version and exit"),
+];
 
-    println!("{}", contents);
+/// The `--help` text, with a line for each of `OPTIONS`.
+pub fn usage() -> String {
+    format!(
+        "Usage: hello_cargo minigrep [options] [--] <query> [<path>...]\n\
+         \x20      hello_cargo minigrep index [options] <dir>\n\
+         \x20      hello_cargo minigrep query [--index <path>] <query>...\n\n\
+         Prints the lines of each file that contain <query>. Directories are \
+         searched recursively,\nand without a path, or for `-`, standard input \
+         is read.\n\nThe index and query commands search a directory through an \
+         index of its words instead;\nto search for the word `index` or `query`, \
+         put `--` before it.\n\nOptions:\n{}",
+        cli::describe(OPTIONS)
+    )
 }
 
-struct Config {
-    query: String,
-    file: String,
+/// Name shown for standard input, which is searched for the path `-`.
+pub const STDIN_NAME: &str = "(standard input)";
+
+/// Writes every matching line as `<line number>:<line>` and reports whether
+/// anything matched.
+///
+/// When more than one file is searched, each line is prefixed with its file's
+/// path. Context lines and highlighting are added as configured, with
+/// `ColorChoice::Auto` treated as `Never` since `out` may not be a terminal.
+/// With `Format::Json`, matches are written as JSON records instead, ending
+/// with a summary of the whole search; the other formats print per-file counts
+/// or file names. Success means a line matched, or with
+/// `Format::FilesWithoutMatch` that a file didn't.
+///
+/// Input is read a line at a time, so memory use is bounded by the longest
+/// line rather than the size of the file. Files that can't be read don't stop
+/// the search; they are reported together in the returned error once the
+/// others have been searched.
+///
+/// With `config.replace`, matches are shown replaced. Adding `config.in_place`
+/// rewrites the files instead of printing anything, and `config.dry_run`
+/// prints the changes as a diff without making them.
+///
+/// With `config.jobs` above 1, several files are searched at once. Each
+/// file's output is then collected in memory and written in file order, so
+/// the result is the same as searching them one after another. Up to
+/// `jobs * parallel::ITEMS_AHEAD_PER_JOB` files' output is held at once, so
+/// memory grows with that many of the largest outputs instead of staying
+/// bounded.
+///
+/// In `Mode::Fuzzy`, matching lines are printed best first instead, across
+/// all files, and without context. That needs every match collected before
+/// the first is printed, so the files are searched one at a time.
+pub fn run(config: Config, out: &mut dyn Write) -> Result<bool, Box<dyn Error>> {
+    let start = Instant::now();
+    let (files, mut errors) = walk::files(&config.paths, &config.walk);
+    let show_paths = config.format.always_shows_paths()
+        || files.len() > 1
+        || config.paths.iter().any(|path| path.is_dir());
+
+    let mut summary =
+        if let (Some(replacement), true) = (&config.replace, config.in_place || config.dry_run) {
+            let mut summary = Summary::default();
+            for file in files {
+                let result = if file == Path::new("-") {
+                    Err(io::Error::other("standard input can't be edited in place"))
+                } else {
+                    replace::edit_file(&file, &config.matcher, replacement, config.dry_run, out)
+                };
+                match result {
+                    Ok(changed_lines) => summary.add_file(changed_lines),
+                    Err(e) => errors.push((file, e)),
+                }
+            }
+            summary
+        } else if config.jobs > 1 && files.len() > 1 && !config.ranked() {
+            run_parallel(&config, files, show_paths, out, &mut errors)?
+        } else {
+            let mut printer = config.printer(out);
+            let mut summary = Summary::default();
+            for file in files {
+                let name = display_name(&file);
+                printer.start_file(show_paths.then(|| name.clone()));
+                match search_file(&file, &config, printer.as_mut(), &name) {
+                    Ok(matched_lines) => summary.add_file(matched_lines),
+                    Err(InputError::Read(e)) => errors.push((file, e)),
+                    Err(InputError::Write(e)) => return Err(e.into()),
+                }
+            }
+            printer.finish()?;
+            summary
+        };
+
+    if config.format == Format::Json {
+        summary.elapsed = start.elapsed();
+        json::write_summary(out, &summary)?;
+    }
+
+    let success = match config.format {
+        Format::FilesWithoutMatch => summary.files_matched < summary.files_searched,
+        _ => summary.files_matched > 0,
+    };
+    if errors.is_empty() {
+        Ok(success)
+    } else {
+        let messages: Vec<String> = errors
+            .iter()
+            .map(|(path, e)| format!("{}: {e}", path.display()))
+            .collect();
+        Err(messages.join("\n").into())
+    }
+}
+
+// Searches `files` on `config.jobs` threads, each file into a buffer of its
+// own, and writes the buffers to `out` in order.
+fn run_parallel(
+    config: &Config,
+    files: Vec<PathBuf>,
+    show_paths: bool,
+    out: &mut dyn Write,
+    errors: &mut Vec<(PathBuf, io::Error)>,
+) -> io::Result<Summary> {
+    let jobs = config.jobs;
+    let has_context =
+        config.format == Format::Text && (config.before_context > 0 || config.after_context > 0);
+    let color = config.color == ColorChoice::Always;
+
+    let config = config.clone();
+    let search = move |file: PathBuf| {
+        let mut buf = Vec::new();
+        let name = display_name(&file);
+        let mut printer = config.printer(&mut buf);
+        printer.start_file(show_paths.then(|| name.clone()));
+        let result = search_file(&file, &config, printer.as_mut(), &name)
+            .and_then(|n| printer.finish().map(|()| n).map_err(InputError::Write));
+        drop(printer);
+        (file, buf, result)
+    };
+
+    // Each buffer was printed as if it were the only file, so the `--`
+    // between files has to be added here.
+    let mut wrote_any = false;
+    let mut summary = Summary::default();
+    parallel::map_ordered(files, jobs, search, |(file, buf, result)| {
+        if !buf.is_empty() {
+            if has_context && wrote_any {
+                output::write_separator(out, color)?;
+            }
+            out.write_all(&buf)?;
+            wrote_any = true;
+        }
+        match result {
+            Ok(matched_lines) => summary.add_file(matched_lines),
+            Err(InputError::Read(e)) => errors.push((file, e)),
+            Err(InputError::Write(e)) => return Err(e),
+        }
+        Ok(())
+    })
+    .map_err(|e| match e {
+        MapError::Emit(e) => e,
+        MapError::WorkerPanicked => io::Error::other("a search thread panicked"),
+    })?;
+    Ok(summary)
+}
+
+// Why searching one input stopped early.
+enum InputError {
+    Read(io::Error),
+    Write(io::Error),
+}
+
+/// How `path` is shown in output: standard input gets a name of its own.
+pub fn display_name(path: &Path) -> String {
+    if path == Path::new("-") {
+        String::from(STDIN_NAME)
+    } else {
+        path.display().to_string()
+    }
+}
+
+// Opens `file`, or standard input for `-`, and searches it.
+fn search_file(
+    file: &Path,
+    config: &Config,
+    printer: &mut dyn Printer,
+    name: &str,
+) -> Result<usize, InputError> {
+    let matched_lines = if file == Path::new("-") {
+        search_reader(io::stdin().lock(), config, printer, name)?
+    } else {
+        let f = File::open(file).map_err(InputError::Read)?;
+        search_reader(BufReader::new(f), config, printer, name)?
+    };
+    printer.finish_file().map_err(InputError::Write)?;
+    Ok(matched_lines)
+}
+
+// Searches one input line by line and returns the number of matching lines,
+// or of non-matching ones when inverted.
+//
+// Once the match limit is reached, reading stops after the lines needed for
+// after-context. Lines that aren't valid UTF-8 are searched with the bad bytes replaced.
+// Input with a NUL byte is treated as binary, like grep does: instead of
+// printing its lines, a match prints "Binary file <name> matches" and the rest
+// of the input is skipped, so it counts as one match. Counting with `-c` prints
+// no lines, so there binary input is searched to the end like any other.
+fn search_reader(
+    mut reader: impl BufRead,
+    config: &Config,
+    printer: &mut dyn Printer,
+    name: &str,
+) -> Result<usize, InputError> {
+    let limit = config.match_limit();
+    let mut trailing_context = config.after_context;
+    let counting = config.format == Format::Count;
+    let mut binary = !counting && reader.fill_buf().map_err(InputError::Read)?.contains(&0);
+    let mut buf = Vec::new();
+    let mut number = 0;
+    let mut offset = 0;
+    let mut matched_lines = 0;
+
+    loop {
+        let limit_reached = limit.is_some_and(|limit| matched_lines >= limit);
+        if limit_reached {
+            if trailing_context == 0 {
+                return Ok(matched_lines);
+            }
+            trailing_context -= 1;
+        }
+        buf.clear();
+        let read = reader
+            .read_until(b'\n', &mut buf)
+            .map_err(InputError::Read)?;
+        if read == 0 {
+            return Ok(matched_lines);
+        }
+        number += 1;
+        let line_offset = offset;
+        offset += read as u64;
+        let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        binary |= !counting && line.contains(&0);
+
+        let line = String::from_utf8_lossy(line);
+        let is_match = !limit_reached && config.matcher.is_match(&line) != config.invert;
+        if binary {
+            if is_match {
+                printer
+                    .binary_file_matches(name)
+                    .map_err(InputError::Write)?;
+                return Ok(1);
+            }
+        } else {
+            if is_match {
+                matched_lines += 1;
+            }
+            printer
+                .line(number, line_offset, &line, is_match)
+                .map_err(InputError::Write)?;
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Config {
+    pub query: String,
+    pub paths: Vec<PathBuf>,
+    pub ignore_case: bool,
+    pub mode: Mode,
+    /// `query` compiled for `mode` and `ignore_case`.
+    pub matcher: Matcher,
+    /// How directories among `paths` are walked.
+    pub walk: WalkOptions,
+    /// Lines of context to print before each match.
+    pub before_context: usize,
+    /// Lines of context to print after each match.
+    pub after_context: usize,
+    pub color: ColorChoice,
+    /// How many files to search at once.
+    pub jobs: usize,
+    pub format: Format,
+    /// Select the lines that don't match instead of those that do.
+    pub invert: bool,
+    /// Stop searching a file after this many selected lines.
+    pub max_count: Option<usize>,
+    /// Text to replace each match with, see `Matcher::replace`.
+    pub replace: Option<String>,
+    /// Rewrite the files with `replace` applied instead of printing lines.
+    pub in_place: bool,
+    /// Show what `in_place` would change as a diff, without changing anything.
+    pub dry_run: bool,
+    /// Print the settings instead of searching.
+    pub print_config: bool,
+    /// Where the settings that aren't defaults came from, in the order they
+    /// were applied, keyed by the names `write_settings` shows.
+    pub sources: Vec<(&'static str, Source)>,
 }
 
 impl Config {
-    fn new(args: &[String]) -> Result<Config, &'static str> {
-        if args.len() < 3 {
-            return Err("not enough arguments");
+    /// Parses `[options] <query> <path>...`, with the `OPTIONS` as `-x` or
+    /// `--long` flags anywhere before a `--`.
+    ///
+    /// - `-i`/`--ignore-case` and `-s`/`--case-sensitive` choose whether case
+    ///   matters. Without either, the search ignores case when the `IGNORE_CASE`
+    ///   environment variable is set; a flag always wins over the variable.
+    /// - `-F`/`--fixed-strings` (the default) matches the query as a substring,
+    ///   `-w`/`--word-regexp` only as a whole word and `-E`/`--regexp` as a
+    ///   regular expression; the last of them wins. `--fuzzy` matches lines
+    ///   with the query's characters in order and prints the best matches
+    ///   first; it ignores case unless the query has an upper-case letter,
+    ///   and can't be used with `--replace`.
+    /// - `--include <glob>` and `--exclude <glob>` (repeatable) filter the files
+    ///   found in directories, and `--no-ignore` stops honoring `.gitignore`.
+    ///   Without any paths, or for the path `-`, standard input is searched.
+    /// - `-A <n>`, `-B <n>` and `-C <n>` print `n` lines of context after,
+    ///   before or around each match; `-A` and `-B` take precedence over `-C`.
+    /// - `--color <when>` is `always`, `never` or `auto` (the default).
+    /// - `--json` writes JSON records instead of lines; context options and
+    ///   `--color` don't apply to it. `-c`/`--count` prints the number of
+    ///   matching lines per file, `-l`/`--files-with-matches` the names of
+    ///   files with a match and `-L`/`--files-without-match` those without
+    ///   one. The last of these formats wins.
+    /// - `-v`/`--invert-match` selects the lines that don't match, and
+    ///   `-m <n>`/`--max-count <n>` stops each file after `n` selected lines.
+    /// - `-r <text>`/`--replace <text>` prints matching lines with each match
+    ///   replaced by `text`. With `--in-place` the files are rewritten instead,
+    ///   and with `--dry-run` the changes are printed as a diff; both need
+    ///   `--replace`.
+    /// - `-j <n>`/`--jobs <n>` searches up to `n` files at once. The default is
+    ///   1, which streams output with bounded memory; above 1 the output of up
+    ///   to `2 * n` files is buffered whole. Output is in the same order for
+    ///   any `n`.
+    ///
+    /// `-h`/`--help` and `-V`/`--version` return `ArgsError::Help` and
+    /// `ArgsError::Version` as long as the rest of the options parse.
+    /// `--print-config` makes the query optional.
+    ///
+    /// Options can also be set in a config file and some in environment
+    /// variables, see `settings`; the command line overrides both.
+    pub fn new(args: &[String]) -> Result<Config, ArgsError> {
+        Config::with_defaults(args, Defaults::load()?)
+    }
+
+    /// Like `new` without a config file, with `ignore_case_env` standing in
+    /// for whether `IGNORE_CASE` is set.
+    pub fn with_env(args: &[String], ignore_case_env: bool) -> Result<Config, ArgsError> {
+        let mut defaults = Defaults::default();
+        if ignore_case_env {
+            defaults.env.push(("IGNORE_CASE", String::new()));
+        }
+        Config::with_defaults(args, defaults)
+    }
+
+    /// Like `new`, with the settings below the command line taken from `defaults`.
+    pub fn with_defaults(args: &[String], defaults: Defaults) -> Result<Config, ArgsError> {
+        let parsed = cli::parse(OPTIONS, args.get(1..).unwrap_or_default())?;
+        let mut options = defaults.options(OPTIONS)?;
+        for found in parsed.options {
+            let source = Source::Flag(found.name.clone());
+            options.push((found, source));
+        }
+
+        let mut ignore_case = false;
+        let mut mode = Mode::Substring;
+        let mut walk = WalkOptions {
+            use_ignore_files: true,
+            ..WalkOptions::default()
+        };
+        let (mut before, mut after, mut context) = (None, None, None);
+        let mut color = ColorChoice::Auto;
+        let mut jobs = 1;
+        let mut format = Format::Text;
+        let mut invert = false;
+        let mut max_count = None;
+        let mut replace = None;
+        let mut in_place = false;
+        let mut dry_run = false;
+        let mut print_config = false;
+        let mut sources = Vec::new();
+
+        for (found, source) in options {
+            // The parser guarantees a value for the options that take one.
+            let value = found.value.as_deref().unwrap_or_default();
+            let invalid = |expected| ArgsError::InvalidValue {
+                option: found.name.clone(),
+                value: value.to_string(),
+                expected,
+            };
+            let count = || value.parse::<usize>().map_err(|_| invalid("a number"));
+            let glob = || Glob::new(value).map_err(ArgsError::InvalidPattern);
+
+            match found.opt.long {
+                "help" => return Err(ArgsError::Help),
+                "version" => return Err(ArgsError::Version),
+                "print-config" => print_config = true,
+                "replace" => replace = Some(value.to_string()),
+                "in-place" => in_place = true,
+                "dry-run" => dry_run = true,
+                "ignore-case" => ignore_case = true,
+                "case-sensitive" => ignore_case = false,
+                "fixed-strings" => mode = Mode::Substring,
+                "word-regexp" => mode = Mode::WholeWord,
+                "regexp" => mode = Mode::Regex,
+                "fuzzy" => mode = Mode::Fuzzy,
+                "include" => walk.include.push(glob()?),
+                "exclude" => walk.exclude.push(glob()?),
+                "no-ignore" => walk.use_ignore_files = false,
+                "after-context" => after = Some(count()?),
+                "before-context" => before = Some(count()?),
+                "context" => context = Some(count()?),
+                "color" => {
+                    color =
+                        ColorChoice::parse(value).ok_or_else(|| invalid("always, never or auto"))?
+                }
+                "json" => format = Format::Json,
+                "count" => format = Format::Count,
+                "files-with-matches" => format = Format::FilesWithMatches,
+                "files-without-match" => format = Format::FilesWithoutMatch,
+                "invert-match" => invert = true,
+                "max-count" => max_count = Some(count()?),
+                "jobs" => match count()? {
+                    0 => return Err(invalid("a number of at least 1")),
+                    n => jobs = n,
+                },
+                other => unreachable!("no handler for --{other}"),
+            }
+            if found.opt.long != "print-config" {
+                sources.push((setting(found.opt.long), source));
+            }
         }
-        let query = args[1].clone();
-        let file = args[2].clone();
-        Ok(Config { query, file })
+
+        if replace.is_none() {
+            for (option, given) in [("--in-place", in_place), ("--dry-run", dry_run)] {
+                if given {
+                    return Err(ArgsError::Requires {
+                        option,
+                        needs: "--replace",
+                    });
+                }
+            }
+        }
+
+        if mode == Mode::Fuzzy && replace.is_some() {
+            return Err(ArgsError::Conflicts {
+                option: "--replace",
+                with: "--fuzzy",
+            });
+        }
+
+        let mut positional = parsed.positional.into_iter();
+        let query = match positional.next() {
+            Some(query) => {
+                sources.push(("query", Source::Argument));
+                query
+            }
+            None if print_config => String::new(),
+            None => return Err(ArgsError::MissingQuery),
+        };
+        let mut paths: Vec<PathBuf> = positional.map(PathBuf::from).collect();
+        if paths.is_empty() {
+            paths.push(PathBuf::from("-"));
+        } else {
+            sources.push(("paths", Source::Argument));
+        }
+
+        let matcher = Matcher::new(&query, mode, ignore_case).map_err(ArgsError::InvalidPattern)?;
+        Ok(Config {
+            query,
+            paths,
+            ignore_case,
+            mode,
+            matcher,
+            walk,
+            before_context: before.or(context).unwrap_or(0),
+            after_context: after.or(context).unwrap_or(0),
+            color,
+            jobs,
+            format,
+            invert,
+            max_count,
+            replace,
+            in_place,
+            dry_run,
+            print_config,
+            sources,
+        })
+    }
+
+    /// Writes each setting in effect, one per line, with where it came from.
+    pub fn write_settings(&self, out: &mut dyn Write) -> io::Result<()> {
+        let last_source = |settings: &[&str]| {
+            settings
+                .iter()
+                .find_map(|&setting| {
+                    self.sources
+                        .iter()
+                        .rev()
+                        .find(|(name, _)| *name == setting)
+                        .map(|(_, source)| source.clone())
+                })
+                .unwrap_or(Source::Default)
+        };
+        let paths: Vec<String> = self
+            .paths
+            .iter()
+            .map(|path| json::string(&path.display().to_string()))
+            .collect();
+        let mode = match self.mode {
+            Mode::Substring => "substring",
+            Mode::WholeWord => "whole-word",
+            Mode::Regex => "regex",
+            Mode::Fuzzy => "fuzzy",
+        };
+        let color = match self.color {
+            ColorChoice::Always => "always",
+            ColorChoice::Never => "never",
+            ColorChoice::Auto => "auto",
+        };
+        let format = match self.format {
+            Format::Text => "text",
+            Format::Json => "json",
+            Format::Count => "count",
+            Format::FilesWithMatches => "files-with-matches",
+            Format::FilesWithoutMatch => "files-without-match",
+        };
+        let max_count = self
+            .max_count
+            .map_or(String::from("none"), |n| n.to_string());
+
+        let mut lines = vec![
+            ("query", json::string(&self.query), last_source(&["query"])),
+            (
+                "paths",
+                format!("[{}]", paths.join(", ")),
+                last_source(&["paths"]),
+            ),
+            (
+                "ignore-case",
+                self.ignore_case.to_string(),
+                last_source(&["ignore-case"]),
+            ),
+            ("mode", mode.to_string(), last_source(&["mode"])),
+        ];
+        // Globs add up across layers, so each has a source of its own.
+        for (setting, globs) in [
+            ("include", &self.walk.include),
+            ("exclude", &self.walk.exclude),
+        ] {
+            let sources = self.sources.iter().filter(|(name, _)| *name == setting);
+            for (glob, (_, source)) in globs.iter().zip(sources) {
+                lines.push((setting, json::string(&glob.to_string()), source.clone()));
+            }
+        }
+        lines.extend([
+            (
+                "no-ignore",
+                (!self.walk.use_ignore_files).to_string(),
+                last_source(&["no-ignore"]),
+            ),
+            (
+                "before-context",
+                self.before_context.to_string(),
+                last_source(&["before-context", "context"]),
+            ),
+            (
+                "after-context",
+                self.after_context.to_string(),
+                last_source(&["after-context", "context"]),
+            ),
+            ("color", color.to_string(), last_source(&["color"])),
+            ("format", format.to_string(), last_source(&["format"])),
+            (
+                "invert-match",
+                self.invert.to_string(),
+                last_source(&["invert-match"]),
+            ),
+            ("max-count", max_count, last_source(&["max-count"])),
+            ("jobs", self.jobs.to_string(), last_source(&["jobs"])),
+            (
+                "replace",
+                self.replace
+                    .as_deref()
+                    .map_or(String::from("none"), json::string),
+                last_source(&["replace"]),
+            ),
+            (
+                "in-place",
+                self.in_place.to_string(),
+                last_source(&["in-place"]),
+            ),
+            (
+                "dry-run",
+                self.dry_run.to_string(),
+                last_source(&["dry-run"]),
+            ),
+        ]);
+
+        let settings: Vec<String> = lines
+            .iter()
+            .map(|(setting, value, _)| format!("{setting} = {value}"))
+            .collect();
+        let width = settings.iter().map(String::len).max().unwrap_or(0);
+        for (setting, (_, _, source)) in settings.iter().zip(&lines) {
+            writeln!(out, "{setting:<width$}  # {source}")?;
+        }
+        Ok(())
+    }
+
+    // How many selected lines of a file to look for. Listing file names only
+    // needs to know whether there is one.
+    fn match_limit(&self) -> Option<usize> {
+        match self.format {
+            Format::FilesWithMatches | Format::FilesWithoutMatch => {
+                Some(self.max_count.map_or(1, |max| max.min(1)))
+            }
+            _ => self.max_count,
+        }
+    }
+
+    // Whether matching lines are printed best first rather than in order.
+    fn ranked(&self) -> bool {
+        self.mode == Mode::Fuzzy && self.format == Format::Text && !self.invert
+    }
+
+    // The printer for `format`, writing to `out`.
+    fn printer<'a>(&'a self, out: &'a mut dyn Write) -> Box<dyn Printer + 'a> {
+        let color = self.color == ColorChoice::Always;
+        if self.ranked() {
+            return Box::new(RankedPrinter::new(out, &self.matcher, color));
+        }
+        match self.format {
+            Format::Text => Box::new(
+                LinePrinter::new(
+                    out,
+                    &self.matcher,
+                    self.before_context,
+                    self.after_context,
+                    color,
+                )
+                .replacing(self.replace.as_deref()),
+            ),
+            Format::Json => Box::new(JsonPrinter::new(out, &self.matcher)),
+            Format::Count => Box::new(CountPrinter::new(out, color)),
+            Format::FilesWithMatches => Box::new(FileListPrinter::new(out, color, true)),
+            Format::FilesWithoutMatch => Box::new(FileListPrinter::new(out, color, false)),
+        }
+    }
+}
+
+// The setting `write_settings` shows for what option `long` changes.
+fn setting(long: &'static str) -> &'static str {
+    match long {
+        "case-sensitive" => "ignore-case",
+        "fixed-strings" | "word-regexp" | "regexp" | "fuzzy" => "mode",
+        "json" | "count" | "files-with-matches" | "files-without-match" => "format",
+        long => long,
+    }
+}
+
+/// Lines of `contents` that contain `query`.
+pub fn search<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    search_with_line_numbers(query, contents)
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect()
+}
+
+/// Like `search`, but pairs each line with its 1-based line number.
+pub fn search_with_line_numbers<'a>(query: &str, contents: &'a str) -> Vec<(usize, &'a str)> {
+    matching_lines(contents, |line| line.contains(query))
+}
+
+/// Lines of `contents` that contain `query`, ignoring case.
+pub fn search_case_insensitive<'a>(query: &str, contents: &'a str) -> Vec<&'a str> {
+    let query = fold_case(query);
+    matching_lines(contents, |line| fold_case(line).contains(&query))
+        .into_iter()
+        .map(|(_, line)| line)
+        .collect()
+}
+
+/// Folds `s` for caseless comparison.
+///
+/// Each character is upper-cased and then lower-cased, which, unlike
+/// `str::to_lowercase` alone, also equates `ß` with `ss` and the final sigma
+/// `ς` with `σ`.
+pub fn fold_case(s: &str) -> String {
+    s.chars()
+        .flat_map(char::to_uppercase)
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn matching_lines(contents: &str, is_match: impl Fn(&str) -> bool) -> Vec<(usize, &str)> {
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| is_match(line))
+        .map(|(i, line)| (i + 1, line))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn one_result() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.";
+
+        assert_eq!(vec!["safe, fast, productive."], search(query, contents));
+    }
+
+    #[test]
+    fn no_result() {
+        assert!(search("monomorphization", "Rust:\nsafe, fast, productive.").is_empty());
+    }
+
+    #[test]
+    fn case_sensitive() {
+        let query = "duct";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Duct tape.";
+
+        assert_eq!(vec!["safe, fast, productive."], search(query, contents));
+    }
+
+    #[test]
+    fn case_insensitive() {
+        let query = "rUsT";
+        let contents = "\
+Rust:
+safe, fast, productive.
+Pick three.
+Trust me.";
+
+        assert_eq!(
+            vec!["Rust:", "Trust me."],
+            search_case_insensitive(query, contents)
+        );
+    }
+
+    #[test]
+    fn case_insensitive_beyond_ascii() {
+        let contents = "Здравствуйте\nStraße\nΣΊΣΥΦΟΣ";
+        assert_eq!(
+            vec!["Здравствуйте"],
+            search_case_insensitive("ЗДРАВСТВУЙТЕ", contents)
+        );
+        assert_eq!(vec!["Straße"], search_case_insensitive("STRASSE", contents));
+        assert_eq!(
+            vec!["ΣΊΣΥΦΟΣ"],
+            search_case_insensitive("σίσυφος", contents)
+        );
+    }
+
+    #[test]
+    fn ignore_case_flag_wins_over_env() {
+        let args = |extra: &[&str]| {
+            ["minigrep"]
+                .iter()
+                .chain(extra)
+                .chain(&["to", POEM])
+                .map(|s| s.to_string())
+                .collect::<Vec<_>>()
+        };
+        assert!(!Config::with_env(&args(&[]), false).unwrap().ignore_case);
+        assert!(Config::with_env(&args(&[]), true).unwrap().ignore_case);
+        assert!(Config::with_env(&args(&["-i"]), false).unwrap().ignore_case);
+        assert!(
+            Config::with_env(&args(&["--ignore-case"]), false)
+                .unwrap()
+                .ignore_case
+        );
+        assert!(
+            !Config::with_env(&args(&["--case-sensitive"]), true)
+                .unwrap()
+                .ignore_case
+        );
+    }
+
+    #[test]
+    fn mode_flags() {
+        let config = |flags: &[&str]| {
+            let args: Vec<String> = ["minigrep"]
+                .iter()
+                .chain(flags)
+                .map(|s| s.to_string())
+                .collect();
+            Config::with_env(&args, false)
+        };
+        assert_eq!(config(&["us", POEM]).unwrap().mode, Mode::Substring);
+        assert_eq!(config(&["-w", "us", POEM]).unwrap().mode, Mode::WholeWord);
+        assert_eq!(
+            config(&["-E", "-w", "us", POEM]).unwrap().mode,
+            Mode::WholeWord
+        );
+        assert_eq!(
+            config(&["-w", "--regexp", "u+s", POEM]).unwrap().mode,
+            Mode::Regex
+        );
+        assert_eq!(config(&["--fuzzy", "us", POEM]).unwrap().mode, Mode::Fuzzy);
+        assert_eq!(
+            config(&["--fuzzy", "-r", "x", "us", POEM]).err(),
+            Some(ArgsError::Conflicts {
+                option: "--replace",
+                with: "--fuzzy"
+            })
+        );
+
+        let err = config(&["-E", "[a-", POEM]).err().unwrap().to_string();
+        assert!(err.starts_with("invalid regular expression `[a-`"), "{err}");
+    }
+
+    #[test]
+    fn paths_and_walk_options() {
+        let args: Vec<String> = [
+            "minigrep",
+            "--include=*.rs",
+            "--exclude",
+            "mod.rs",
+            "--no-ignore",
+            "fn",
+            "src",
+            "tests",
+        ]
+        .map(String::from)
+        .to_vec();
+        let config = Config::with_env(&args, false).unwrap();
+        assert_eq!(config.paths, [PathBuf::from("src"), PathBuf::from("tests")]);
+        assert_eq!(config.walk.include, [Glob::new("*.rs").unwrap()]);
+        assert_eq!(config.walk.exclude, [Glob::new("mod.rs").unwrap()]);
+        assert!(!config.walk.use_ignore_files);
+
+        let args = ["minigrep", "fn", "src", "--include"].map(String::from);
+        assert_eq!(
+            Config::with_env(&args, false).err().unwrap(),
+            ArgsError::MissingValue(String::from("--include"))
+        );
+    }
+
+    #[test]
+    fn context_options() {
+        let config = |flags: &[&str]| {
+            let args: Vec<String> = ["minigrep"]
+                .iter()
+                .chain(flags)
+                .chain(&["to", POEM])
+                .map(|s| s.to_string())
+                .collect();
+            Config::with_env(&args, false).map(|c| (c.before_context, c.after_context))
+        };
+        assert_eq!(config(&[]), Ok((0, 0)));
+        assert_eq!(config(&["-C", "2"]), Ok((2, 2)));
+        assert_eq!(config(&["-A1", "-C3"]), Ok((3, 1)));
+        assert_eq!(config(&["--before-context=4", "-C", "1"]), Ok((4, 1)));
+        assert_eq!(
+            config(&["-A", "x"]),
+            Err(ArgsError::InvalidValue {
+                option: String::from("-A"),
+                value: String::from("x"),
+                expected: "a number"
+            })
+        );
+    }
+
+    #[test]
+    fn jobs_option() {
+        let jobs = |flags: &[&str]| {
+            let args: Vec<String> = ["minigrep"]
+                .iter()
+                .chain(flags)
+                .chain(&["to", POEM])
+                .map(|s| s.to_string())
+                .collect();
+            Config::with_env(&args, false).map(|c| c.jobs)
+        };
+        assert_eq!(jobs(&[]), Ok(1));
+        assert_eq!(jobs(&["-j", "3"]), Ok(3));
+        assert_eq!(jobs(&["-j1"]), Ok(1));
+        assert_eq!(jobs(&["--jobs=8"]), Ok(8));
+        assert_eq!(
+            jobs(&["-j", "0"]).unwrap_err().to_string(),
+            "invalid value `0` for -j; expected a number of at least 1"
+        );
+    }
+
+    #[test]
+    fn output_mode_flags() {
+        let config = |flags: &[&str]| {
+            let args: Vec<String> = ["minigrep"]
+                .iter()
+                .chain(flags)
+                .chain(&["to", POEM])
+                .map(|s| s.to_string())
+                .collect();
+            Config::with_env(&args, false).unwrap()
+        };
+        assert_eq!(config(&[]).format, Format::Text);
+        assert_eq!(config(&["-c"]).format, Format::Count);
+        assert_eq!(config(&["-c", "--json"]).format, Format::Json);
+        assert_eq!(config(&["--json", "-l"]).format, Format::FilesWithMatches);
+        assert_eq!(
+            config(&["--files-without-match"]).format,
+            Format::FilesWithoutMatch
+        );
+
+        let plain = config(&[]);
+        assert!(!plain.invert);
+        assert_eq!(plain.max_count, None);
+        assert!(config(&["--invert-match"]).invert);
+        assert_eq!(config(&["-m", "3"]).max_count, Some(3));
+        assert_eq!(config(&["-m0"]).max_count, Some(0));
+        assert_eq!(config(&["--max-count=2", "-l"]).match_limit(), Some(1));
+        assert_eq!(config(&["-m", "0", "-L"]).match_limit(), Some(0));
+    }
+
+    #[test]
+    fn help_version_and_double_dash() {
+        let config = |args: &[&str]| {
+            let args: Vec<String> = ["minigrep"]
+                .iter()
+                .chain(args)
+                .map(|s| s.to_string())
+                .collect();
+            Config::with_env(&args, false)
+        };
+        assert_eq!(config(&["--help"]).err(), Some(ArgsError::Help));
+        assert_eq!(config(&["-V", "query"]).err(), Some(ArgsError::Version));
+        assert_eq!(
+            config(&["-h", "--bogus"]).err(),
+            Some(ArgsError::UnknownOption(String::from("--bogus")))
+        );
+        assert_eq!(config(&["-i"]).err(), Some(ArgsError::MissingQuery));
+
+        let config = config(&["-i", "--", "-v", "-"]).unwrap();
+        assert_eq!(config.query, "-v");
+        assert!(!config.invert);
+        assert_eq!(config.paths, [PathBuf::from("-")]);
+
+        for opt in OPTIONS {
+            assert!(usage().contains(&format!("--{}", opt.long)), "{}", opt.long);
+        }
+    }
+
+    #[test]
+    fn settings_show_their_sources() {
+        let args = ["minigrep", "-C", "2", "-A1", "frog"].map(String::from);
+        let defaults = Defaults {
+            file: Some((
+                PathBuf::from("config.toml"),
+                String::from("exclude = [\"target\"]\nword-regexp = true\n"),
+            )),
+            env: vec![("IGNORE_CASE", String::from("1"))],
+        };
+        let config = Config::with_defaults(&args, defaults).unwrap();
+        assert!(config.ignore_case);
+        assert_eq!(config.mode, Mode::WholeWord);
+
+        let mut out = Vec::new();
+        config.write_settings(&mut out).unwrap();
+        let settings = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = settings.lines().collect();
+        assert_eq!(lines[0], "query = \"frog\"        # command line argument");
+        assert_eq!(lines[1], "paths = [\"-\"]         # default");
+        assert_eq!(
+            lines[2],
+            "ignore-case = true    # environment variable IGNORE_CASE"
+        );
+        assert_eq!(lines[3], "mode = whole-word     # config file config.toml");
+        assert_eq!(
+            lines[4],
+            "exclude = \"target\"    # config file config.toml"
+        );
+        assert_eq!(lines[6], "before-context = 2    # command line -C");
+        assert_eq!(lines[7], "after-context = 1     # command line -A");
+    }
+
+    #[test]
+    fn line_numbers_are_one_based() {
+        let contents = fs::read_to_string(POEM).unwrap();
+        assert_eq!(
+            search_with_line_numbers("nobody", &contents),
+            vec![(1, "I'm nobody! Who are you?"), (2, "Are you nobody, too?")]
+        );
     }
 }
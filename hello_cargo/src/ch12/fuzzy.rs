@@ -0,0 +1,174 @@
+// Fuzzy matching for minigrep's `--fuzzy` mode: the query's characters must
+// appear in the line in order, but not necessarily next to each other, so
+// `hwpub` matches "How public". Each match gets a score, higher for a better
+// match, so results can be ranked:
+//
+//   - every matched character scores `MATCH`
+//   - a character right after the previous matched one adds `CONSECUTIVE`
+//   - a character at the start of a word adds `BOUNDARY` (`CAMEL_CASE` for an
+//     upper-case letter after a lower-case one), twice for the first one
+//   - skipping characters between two matched ones costs `GAP_START`, plus
+//     `GAP` for every skipped character after the first
+//
+// Among all the ways the query fits into a line, the best scoring one is
+// found with dynamic programming, in time proportional to the query's length
+// times the line's.
+
+const MATCH: i64 = 16;
+const CONSECUTIVE: i64 = 8;
+const BOUNDARY: i64 = 8;
+const CAMEL_CASE: i64 = 7;
+const GAP_START: i64 = 3;
+const GAP: i64 = 1;
+
+/// How well a query matched a line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// Byte positions of the matched characters in the line.
+    pub positions: Vec<usize>,
+}
+
+/// Whether `query` is a subsequence of `line`.
+pub fn is_match(query: &[char], line: &str, ignore_case: bool) -> bool {
+    let mut rest = line.chars();
+    query
+        .iter()
+        .all(|&q| rest.any(|c| same_char(q, c, ignore_case)))
+}
+
+fn same_char(q: char, c: char, ignore_case: bool) -> bool {
+    if ignore_case {
+        q.to_lowercase().eq(c.to_lowercase())
+    } else {
+        q == c
+    }
+}
+
+/// The best way `query` fits into `line` as a subsequence, if it does.
+pub fn best_match(query: &[char], line: &str, ignore_case: bool) -> Option<FuzzyMatch> {
+    // Most lines don't match at all; a greedy scan finds that out cheaply.
+    if !is_match(query, line, ignore_case) {
+        return None;
+    }
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let eq = |q, c| same_char(q, c, ignore_case);
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    let bonus: Vec<i64> = chars
+        .iter()
+        .enumerate()
+        .map(|(j, &(_, c))| match j.checked_sub(1).map(|k| chars[k].1) {
+            None => BOUNDARY,
+            Some(prev) if !prev.is_alphanumeric() && c.is_alphanumeric() => BOUNDARY,
+            Some(prev) if prev.is_lowercase() && c.is_uppercase() => CAMEL_CASE,
+            _ => 0,
+        })
+        .collect();
+
+    // scores[i][j]: the best score for the first i + 1 query characters with
+    // the last one at chars[j]; from[i][j]: where query[i - 1] was then.
+    let n = chars.len();
+    let mut scores = vec![vec![None; n]; query.len()];
+    let mut from = vec![vec![0; n]; query.len()];
+
+    for (j, &(_, c)) in chars.iter().enumerate() {
+        if eq(query[0], c) {
+            scores[0][j] = Some(MATCH + 2 * bonus[j]);
+        }
+    }
+    for i in 1..query.len() {
+        // The best predecessor at least two characters back, scored so that
+        // a gap penalty can be subtracted for any j: score + GAP * k.
+        let mut best_before_gap: Option<(i64, usize)> = None;
+        for j in 1..n {
+            if j >= 2
+                && let Some(score) = scores[i - 1][j - 2]
+            {
+                let adjusted = score + GAP * (j - 2) as i64;
+                if best_before_gap.is_none_or(|(best, _)| adjusted > best) {
+                    best_before_gap = Some((adjusted, j - 2));
+                }
+            }
+            if !eq(query[i], chars[j].1) {
+                continue;
+            }
+            let consecutive = scores[i - 1][j - 1].map(|score| (score + CONSECUTIVE, j - 1));
+            let after_gap = best_before_gap.map(|(adjusted, k)| {
+                // Skipping j - k - 1 characters.
+                (adjusted - GAP * (j - 2) as i64 - GAP_START, k)
+            });
+            let best = match (consecutive, after_gap) {
+                (Some(a), Some(b)) => Some(if a.0 >= b.0 { a } else { b }),
+                (a, b) => a.or(b),
+            };
+            if let Some((score, k)) = best {
+                scores[i][j] = Some(MATCH + bonus[j] + score);
+                from[i][j] = k;
+            }
+        }
+    }
+
+    let last = query.len() - 1;
+    let (mut j, score) = scores[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, score)| score.map(|score| (j, score)))
+        .max_by_key(|&(j, score)| (score, std::cmp::Reverse(j)))?;
+    let mut positions = vec![0; query.len()];
+    for i in (0..query.len()).rev() {
+        positions[i] = chars[j].0;
+        j = from[i][j];
+    }
+    Some(FuzzyMatch { score, positions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fuzzy(query: &str, line: &str) -> Option<FuzzyMatch> {
+        let query: Vec<char> = query.chars().collect();
+        best_match(&query, line, true)
+    }
+
+    fn score(query: &str, line: &str) -> i64 {
+        fuzzy(query, line).unwrap().score
+    }
+
+    #[test]
+    fn subsequences_match() {
+        assert_eq!(
+            fuzzy("hwpub", "How public").unwrap().positions,
+            [0, 2, 4, 5, 6]
+        );
+        assert!(fuzzy("frg", "How public, like a frog").is_some());
+        assert!(fuzzy("gorf", "How public, like a frog").is_none());
+        assert!(best_match(&['H'], "how", false).is_none());
+        assert!(best_match(&['H'], "how", true).is_some());
+    }
+
+    #[test]
+    fn consecutive_characters_score_higher() {
+        assert!(score("frog", "a frog") > score("frog", "f r o g"));
+        assert!(score("ab", "xab") > score("ab", "xaxb"));
+    }
+
+    #[test]
+    fn word_starts_score_higher() {
+        assert!(score("pl", "public, like") > score("pl", "apples"));
+        assert!(score("fb", "fooBar") > score("fb", "foobar"));
+        // The best alignment is found even when a greedy one is worse.
+        assert_eq!(fuzzy("lb", "label bad").unwrap().positions, [0, 6]);
+    }
+
+    #[test]
+    fn positions_are_byte_offsets() {
+        assert_eq!(fuzzy("ße", "Große Straße").unwrap().positions, [3, 5]);
+    }
+}
@@ -0,0 +1,183 @@
+// Shell-style glob patterns for minigrep's --include/--exclude options and
+// ignore files.
+//
+//   *       any run of characters except `/`
+//   **      any run of characters, `/` included; `**/` also matches nothing
+//   ?       one character except `/`
+//   [a-z]   one character from a class, `[!a-z]` or `[^a-z]` to negate
+//   \*      a literal `*` (any character can be escaped)
+
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Literal(char),
+    AnyChar,
+    Star,
+    DoubleStar,
+    // `**/`: zero or more whole directories.
+    AnyDirs,
+    Class {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+}
+
+/// A compiled glob pattern.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Glob {
+    pattern: String,
+    tokens: Vec<Token>,
+}
+
+impl Glob {
+    pub fn new(pattern: &str) -> Result<Glob, String> {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '*' if chars.peek() == Some(&'*') => {
+                    chars.next();
+                    if chars.peek() == Some(&'/') {
+                        chars.next();
+                        Token::AnyDirs
+                    } else {
+                        Token::DoubleStar
+                    }
+                }
+                '*' => Token::Star,
+                '?' => Token::AnyChar,
+                '\\' => match chars.next() {
+                    Some(escaped) => Token::Literal(escaped),
+                    None => return Err(format!("invalid glob `{pattern}`: trailing `\\`")),
+                },
+                '[' => {
+                    let negated = matches!(chars.peek(), Some('!' | '^'));
+                    if negated {
+                        chars.next();
+                    }
+                    let mut ranges = Vec::new();
+                    loop {
+                        let start = match chars.next() {
+                            // A `]` right after `[` is part of the class.
+                            Some(']') if !ranges.is_empty() => break,
+                            Some(c) => c,
+                            None => return Err(format!("invalid glob `{pattern}`: unclosed `[`")),
+                        };
+                        let mut lookahead = chars.clone();
+                        if lookahead.next() == Some('-')
+                            && lookahead.peek().is_some_and(|&c| c != ']')
+                        {
+                            chars.next();
+                            let end = chars.next().unwrap();
+                            ranges.push((start, end));
+                        } else {
+                            ranges.push((start, start));
+                        }
+                    }
+                    Token::Class { negated, ranges }
+                }
+                c => Token::Literal(c),
+            };
+            tokens.push(token);
+        }
+
+        Ok(Glob {
+            pattern: pattern.to_string(),
+            tokens,
+        })
+    }
+
+    /// Whether the whole of `text` matches.
+    pub fn is_match(&self, text: &str) -> bool {
+        let text: Vec<char> = text.chars().collect();
+        matches(&self.tokens, &text)
+    }
+
+    /// Whether the pattern names a path rather than just a file name.
+    pub fn has_slash(&self) -> bool {
+        self.pattern.contains('/')
+    }
+}
+
+impl fmt::Display for Glob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+fn matches(tokens: &[Token], text: &[char]) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return text.is_empty();
+    };
+
+    match token {
+        Token::Star => {
+            // Try every split point up to the next `/`.
+            let limit = text.iter().position(|&c| c == '/').unwrap_or(text.len());
+            (0..=limit).any(|i| matches(rest, &text[i..]))
+        }
+        Token::DoubleStar => (0..=text.len()).any(|i| matches(rest, &text[i..])),
+        Token::AnyDirs => {
+            matches(rest, text)
+                || (1..=text.len()).any(|i| text[i - 1] == '/' && matches(rest, &text[i..]))
+        }
+        Token::AnyChar => match text.split_first() {
+            Some((&c, tail)) => c != '/' && matches(rest, tail),
+            None => false,
+        },
+        Token::Literal(expected) => match text.split_first() {
+            Some((c, tail)) => c == expected && matches(rest, tail),
+            None => false,
+        },
+        Token::Class { negated, ranges } => match text.split_first() {
+            Some((&c, tail)) => {
+                let in_class = ranges.iter().any(|&(lo, hi)| lo <= c && c <= hi);
+                c != '/' && in_class != *negated && matches(rest, tail)
+            }
+            None => false,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glob(pattern: &str) -> Glob {
+        Glob::new(pattern).unwrap()
+    }
+
+    #[test]
+    fn star_and_question_mark() {
+        assert!(glob("*.rs").is_match("ch12.rs"));
+        assert!(!glob("*.rs").is_match("ch12.rs.bak"));
+        assert!(!glob("*.rs").is_match("ch12/ch12.rs"));
+        assert!(glob("ch?_?.rs").is_match("ch8_1.rs"));
+        assert!(!glob("ch?_?.rs").is_match("ch10_1.rs"));
+    }
+
+    #[test]
+    fn double_star_crosses_directories() {
+        assert!(glob("src/**/*.rs").is_match("src/ch12/ch12.rs"));
+        assert!(glob("src/**/*.rs").is_match("src/lib.rs"));
+        assert!(glob("**/target").is_match("target"));
+        assert!(glob("src/**").is_match("src/ch12/poem.txt"));
+    }
+
+    #[test]
+    fn classes_and_escapes() {
+        assert!(glob("ch[0-9].rs").is_match("ch7.rs"));
+        assert!(!glob("ch[!0-9].rs").is_match("ch7.rs"));
+        assert!(glob("[]x]").is_match("]"));
+        assert!(glob(r"\*.txt").is_match("*.txt"));
+        assert!(!glob(r"\*.txt").is_match("poem.txt"));
+    }
+
+    #[test]
+    fn invalid_patterns() {
+        assert!(Glob::new("[abc").is_err());
+        assert!(Glob::new("abc\\").is_err());
+    }
+}
@@ -0,0 +1,874 @@
+// An inverted index, so that repeated searches of a large directory don't
+// read every file again:
+//
+//   hello_cargo minigrep index src                     writes src/.minigrep-index
+//   hello_cargo minigrep query --index src 'frog OR "admiring bog"'
+//
+// The index maps every word, lower-cased, to where it occurs: the file, the
+// line number, the line's byte offset and the word's position in the line.
+// Queries are answered from those postings alone; the matching lines are
+// then read back by seeking to their offsets, to print them.
+//
+// Each file's modification time is recorded with it. `index` only re-reads
+// the files that changed since it last ran, and `query` re-reads changed
+// files rather than trust their old postings, and leaves out deleted ones.
+// Files added since the last `index` are found when it runs again.
+//
+// The index is a text file:
+//
+//   minigrep-index 1
+//   file 1700000000 123456789 ch12/poem.txt     (mtime seconds, nanoseconds, path)
+//   term frog 0:7:142:4                         (file:line:offset:position ...)
+//
+// with paths relative to the directory the index is in. Paths are stored as
+// text to the end of the line, so files whose names aren't UTF-8 or contain a
+// line break can't be indexed; `index` reports them as errors.
+
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, UNIX_EPOCH};
+
+use super::glob::Glob;
+use super::walk::{self, WalkOptions};
+use crate::cli::{self, ArgsError, Opt};
+use crate::fsutil::TempFile;
+
+/// Name of the index file, in the directory it indexes.
+pub const INDEX_FILE: &str = ".minigrep-index";
+
+const HEADER: &str = "minigrep-index 1";
+
+/// One occurrence of a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Posting {
+    /// Index into `Index::files`.
+    pub file: usize,
+    pub line: usize,
+    /// Byte position of the line in its file.
+    pub offset: u64,
+    /// Which word of the line it is, counting from 0.
+    pub position: usize,
+}
+
+/// A file in the index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IndexedFile {
+    /// Relative to the index's root.
+    pub path: PathBuf,
+    /// Modification time, since the Unix epoch.
+    pub modified: Duration,
+}
+
+/// The words of the files below `root` and where they occur.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Index {
+    pub root: PathBuf,
+    // `None` for files dropped since the index was built or loaded.
+    files: Vec<Option<IndexedFile>>,
+    terms: BTreeMap<String, Vec<Posting>>,
+}
+
+/// A matching line found through the index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hit {
+    pub path: PathBuf,
+    pub line: usize,
+    pub offset: u64,
+}
+
+/// The words of `line`, lower-cased: runs of letters, digits and `_`.
+pub fn words(line: &str) -> impl Iterator<Item = String> + '_ {
+    line.split(|c: char| !(c.is_alphanumeric() || c == '_'))
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+impl Index {
+    /// An empty index for the directory `root`.
+    pub fn new(root: &Path) -> Index {
+        Index {
+            root: root.to_path_buf(),
+            ..Index::default()
+        }
+    }
+
+    /// The index file for `path`, which names either the file itself or the
+    /// directory it is in.
+    pub fn file_for(path: &Path) -> PathBuf {
+        if path.is_dir() {
+            path.join(INDEX_FILE)
+        } else {
+            path.to_path_buf()
+        }
+    }
+
+    /// The files in the index, in the order they were added.
+    pub fn files(&self) -> impl Iterator<Item = &IndexedFile> {
+        self.files.iter().flatten()
+    }
+
+    /// Reads the index file at `path`.
+    pub fn load(path: &Path) -> io::Result<Index> {
+        let text = fs::read_to_string(path)?;
+        let root = path.parent().unwrap_or(Path::new("."));
+        Index::parse(root, &text).map_err(|reason| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{}: {reason}", path.display()),
+            )
+        })
+    }
+
+    /// Parses the contents of an index file for the directory `root`.
+    pub fn parse(root: &Path, text: &str) -> Result<Index, String> {
+        let mut lines = text.lines().enumerate();
+        if lines.next().map(|(_, line)| line) != Some(HEADER) {
+            return Err(format!("not a minigrep index (expected `{HEADER}`)"));
+        }
+        let mut index = Index::new(root);
+
+        for (i, line) in lines {
+            let error = |reason: &str| format!("line {}: {reason}", i + 1);
+            if let Some(rest) = line.strip_prefix("file ") {
+                let mut fields = rest.splitn(3, ' ');
+                let (Some(secs), Some(nanos), Some(path)) =
+                    (fields.next(), fields.next(), fields.next())
+                else {
+                    return Err(error("expected `file <seconds> <nanoseconds> <path>`"));
+                };
+                let secs = secs.parse().map_err(|_| error("invalid seconds"))?;
+                let nanos = nanos.parse().map_err(|_| error("invalid nanoseconds"))?;
+                index.files.push(Some(IndexedFile {
+                    path: PathBuf::from(path),
+                    modified: Duration::new(secs, nanos),
+                }));
+            } else if let Some(rest) = line.strip_prefix("term ") {
+                let mut fields = rest.split(' ');
+                let term = fields.next().unwrap_or_default();
+                let postings = fields
+                    .map(|posting| {
+                        let numbers: Vec<&str> = posting.split(':').collect();
+                        let &[file, line, offset, position] = numbers.as_slice() else {
+                            return Err(error("expected `file:line:offset:position`"));
+                        };
+                        let posting = Posting {
+                            file: file.parse().map_err(|_| error("invalid file number"))?,
+                            line: line.parse().map_err(|_| error("invalid line number"))?,
+                            offset: offset.parse().map_err(|_| error("invalid offset"))?,
+                            position: position.parse().map_err(|_| error("invalid position"))?,
+                        };
+                        if posting.file >= index.files.len() {
+                            return Err(error("posting for an unknown file"));
+                        }
+                        Ok(posting)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                index.terms.insert(term.to_string(), postings);
+            } else if !line.is_empty() {
+                return Err(error("expected `file` or `term`"));
+            }
+        }
+        Ok(index)
+    }
+
+    /// Writes the index to `INDEX_FILE` in its root, replacing any older one
+    /// in a single step.
+    pub fn save(&self) -> io::Result<PathBuf> {
+        let path = self.root.join(INDEX_FILE);
+        let mut temp = TempFile::next_to(&path)?;
+        self.write(&mut temp.writer)?;
+        temp.replace(&path)?;
+        Ok(path)
+    }
+
+    /// Writes the index in its file format, numbering the files afresh.
+    pub fn write(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "{HEADER}")?;
+        let mut numbers = vec![None; self.files.len()];
+        for (number, (id, file)) in self
+            .files
+            .iter()
+            .enumerate()
+            .filter_map(|(id, file)| file.as_ref().map(|file| (id, file)))
+            .enumerate()
+        {
+            numbers[id] = Some(number);
+            writeln!(
+                out,
+                "file {} {} {}",
+                file.modified.as_secs(),
+                file.modified.subsec_nanos(),
+                file.path.display()
+            )?;
+        }
+        for (term, postings) in &self.terms {
+            write!(out, "term {term}")?;
+            for posting in postings {
+                if let Some(number) = numbers[posting.file] {
+                    write!(
+                        out,
+                        " {number}:{}:{}:{}",
+                        posting.line, posting.offset, posting.position
+                    )?;
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Brings the index up to date with the files below its root: new files
+    /// are added, changed ones read again and deleted ones dropped. Returns
+    /// how many files were read, and the paths that couldn't be.
+    pub fn update(&mut self, options: &WalkOptions) -> (usize, Vec<(PathBuf, io::Error)>) {
+        let mut options = options.clone();
+        options
+            .exclude
+            .push(Glob::new(INDEX_FILE).expect("the index file name is a glob"));
+        let (found, mut errors) = walk::files(std::slice::from_ref(&self.root), &options);
+        let found: Vec<PathBuf> = found
+            .iter()
+            .filter_map(|path| path.strip_prefix(&self.root).ok())
+            .map(Path::to_path_buf)
+            .collect();
+
+        let wanted: HashSet<&PathBuf> = found.iter().collect();
+        let mut known = HashSet::new();
+        let mut stale = HashSet::new();
+        for (id, file) in self.files.iter().enumerate() {
+            let Some(file) = file else { continue };
+            if !wanted.contains(&file.path) || self.modified(&file.path).ok() != Some(file.modified)
+            {
+                stale.insert(id);
+            } else {
+                known.insert(file.path.clone());
+            }
+        }
+        self.drop_files(&stale);
+
+        let mut read = 0;
+        for path in found {
+            if known.contains(&path) {
+                continue;
+            }
+            if !storable(&path) {
+                let e = io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "the index can't store a name that isn't UTF-8 or has a line break",
+                );
+                errors.push((self.root.join(&path), e));
+                continue;
+            }
+            match self.add_file(&path) {
+                Ok(()) => read += 1,
+                Err(e) => errors.push((self.root.join(&path), e)),
+            }
+        }
+        (read, errors)
+    }
+
+    /// Re-reads the files that changed since they were indexed and drops
+    /// those that no longer exist, without looking for new files.
+    pub fn refresh(&mut self) -> Vec<(PathBuf, io::Error)> {
+        let mut changed = Vec::new();
+        let mut stale = HashSet::new();
+        for (id, file) in self.files.iter().enumerate() {
+            let Some(file) = file else { continue };
+            match self.modified(&file.path) {
+                Ok(modified) if modified == file.modified => {}
+                Ok(_) => {
+                    stale.insert(id);
+                    changed.push(file.path.clone());
+                }
+                Err(_) => {
+                    stale.insert(id);
+                }
+            }
+        }
+        self.drop_files(&stale);
+
+        let mut errors = Vec::new();
+        for path in changed {
+            if let Err(e) = self.add_file(&path) {
+                errors.push((self.root.join(&path), e));
+            }
+        }
+        errors
+    }
+
+    // The modification time of `path`, relative to the root.
+    fn modified(&self, path: &Path) -> io::Result<Duration> {
+        fs::metadata(self.root.join(path))?
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_err(io::Error::other)
+    }
+
+    // Reads the file at `path`, relative to the root, and adds its words.
+    // Binary files are recorded without any, so they aren't read every time.
+    fn add_file(&mut self, path: &Path) -> io::Result<()> {
+        let modified = self.modified(path)?;
+        let mut reader = BufReader::new(File::open(self.root.join(path))?);
+        let binary = reader.fill_buf()?.contains(&0);
+        let file = self.files.len();
+        self.files.push(Some(IndexedFile {
+            path: path.to_path_buf(),
+            modified,
+        }));
+        if binary {
+            return Ok(());
+        }
+
+        let mut buf = Vec::new();
+        let mut line = 0;
+        let mut offset = 0;
+        loop {
+            buf.clear();
+            let read = reader.read_until(b'\n', &mut buf)?;
+            if read == 0 {
+                return Ok(());
+            }
+            line += 1;
+            for (position, word) in words(&String::from_utf8_lossy(&buf)).enumerate() {
+                self.terms.entry(word).or_default().push(Posting {
+                    file,
+                    line,
+                    offset,
+                    position,
+                });
+            }
+            offset += read as u64;
+        }
+    }
+
+    fn drop_files(&mut self, ids: &HashSet<usize>) {
+        if ids.is_empty() {
+            return;
+        }
+        for &id in ids {
+            self.files[id] = None;
+        }
+        for postings in self.terms.values_mut() {
+            postings.retain(|posting| !ids.contains(&posting.file));
+        }
+        self.terms.retain(|_, postings| !postings.is_empty());
+    }
+
+    /// The lines matching `query`, by path and then line number.
+    pub fn search(&self, query: &Query) -> Vec<Hit> {
+        let mut hits: Vec<Hit> = self
+            .lines(query)
+            .into_iter()
+            .filter_map(|((file, line), offset)| {
+                let file = self.files[file].as_ref()?;
+                Some(Hit {
+                    path: self.root.join(&file.path),
+                    line,
+                    offset,
+                })
+            })
+            .collect();
+        hits.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+        hits
+    }
+
+    // The (file, line) pairs matching `query`, with each line's offset.
+    fn lines(&self, query: &Query) -> BTreeMap<(usize, usize), u64> {
+        match query {
+            Query::Words(words) => {
+                let postings: Vec<&[Posting]> = words
+                    .iter()
+                    .map(|word| self.terms.get(word).map_or(&[][..], Vec::as_slice))
+                    .collect();
+                let Some((first, rest)) = postings.split_first() else {
+                    return BTreeMap::new();
+                };
+                // The rest of a phrase must follow the first word on its line.
+                let later: Vec<HashSet<(usize, usize, usize)>> = rest
+                    .iter()
+                    .map(|postings| {
+                        postings
+                            .iter()
+                            .map(|p| (p.file, p.line, p.position))
+                            .collect()
+                    })
+                    .collect();
+                first
+                    .iter()
+                    .filter(|p| {
+                        later.iter().enumerate().all(|(i, positions)| {
+                            positions.contains(&(p.file, p.line, p.position + i + 1))
+                        })
+                    })
+                    .map(|p| ((p.file, p.line), p.offset))
+                    .collect()
+            }
+            Query::And(left, right) => {
+                let right = self.lines(right);
+                self.lines(left)
+                    .into_iter()
+                    .filter(|(line, _)| right.contains_key(line))
+                    .collect()
+            }
+            Query::Or(left, right) => {
+                let mut lines = self.lines(left);
+                lines.extend(self.lines(right));
+                lines
+            }
+        }
+    }
+}
+
+/// A query for `Index::search`, matched line by line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    /// A word, or a phrase of words next to each other.
+    Words(Vec<String>),
+    And(Box<Query>, Box<Query>),
+    Or(Box<Query>, Box<Query>),
+}
+
+impl Query {
+    /// Parses words, `"quoted phrases"`, `AND` and `OR`. Words next to each
+    /// other without an operator must all be on the line, as with `AND`,
+    /// which binds more tightly than `OR`. Case doesn't matter.
+    pub fn parse(text: &str) -> Result<Query, String> {
+        #[derive(PartialEq)]
+        enum Token {
+            Words(Vec<String>),
+            And,
+            Or,
+        }
+
+        let mut tokens = Vec::new();
+        let mut rest = text.trim_start();
+        while !rest.is_empty() {
+            let (token, after) = if let Some(quoted) = rest.strip_prefix('"') {
+                let end = quoted
+                    .find('"')
+                    .ok_or_else(|| String::from("unterminated quote"))?;
+                (&quoted[..end], &quoted[end + 1..])
+            } else {
+                let end = rest.find(char::is_whitespace).unwrap_or(rest.len());
+                (&rest[..end], &rest[end..])
+            };
+            let quoted = rest.starts_with('"');
+            rest = after.trim_start();
+
+            match token {
+                "AND" if !quoted => tokens.push(Token::And),
+                "OR" if !quoted => tokens.push(Token::Or),
+                _ => {
+                    let words: Vec<String> = words(token).collect();
+                    if !words.is_empty() {
+                        tokens.push(Token::Words(words));
+                    }
+                }
+            }
+        }
+
+        let mut alternatives = Vec::new();
+        for group in tokens.split(|token| *token == Token::Or) {
+            let mut terms = Vec::new();
+            for (i, token) in group.iter().enumerate() {
+                match token {
+                    Token::Words(words) => terms.push(Query::Words(words.clone())),
+                    Token::And if i > 0 && i + 1 < group.len() && group[i + 1] != Token::And => {}
+                    _ => return Err(String::from("AND and OR need a word on each side")),
+                }
+            }
+            let query = terms
+                .into_iter()
+                .reduce(|left, right| Query::And(Box::new(left), Box::new(right)))
+                .ok_or_else(|| {
+                    if group.is_empty() && !tokens.is_empty() {
+                        String::from("AND and OR need a word on each side")
+                    } else {
+                        String::from("the query has no words")
+                    }
+                })?;
+            alternatives.push(query);
+        }
+        Ok(alternatives
+            .into_iter()
+            .reduce(|left, right| Query::Or(Box::new(left), Box::new(right)))
+            .expect("split always yields a group"))
+    }
+}
+
+/// Writes each hit as `path:line:text`, reading only the lines themselves.
+pub fn write_hits(hits: &[Hit], out: &mut dyn Write) -> io::Result<()> {
+    let mut open: Option<(&Path, BufReader<File>)> = None;
+    let mut buf = Vec::new();
+    for hit in hits {
+        if open.as_ref().is_none_or(|(path, _)| *path != hit.path) {
+            open = Some((&hit.path, BufReader::new(File::open(&hit.path)?)));
+        }
+        let (_, reader) = open.as_mut().expect("opened above");
+        reader.seek(SeekFrom::Start(hit.offset))?;
+        buf.clear();
+        reader.read_until(b'\n', &mut buf)?;
+        let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        writeln!(
+            out,
+            "{}:{}:{}",
+            hit.path.display(),
+            hit.line,
+            String::from_utf8_lossy(line)
+        )?;
+    }
+    Ok(())
+}
+
+/// The options `minigrep index` understands.
+#[rustfmt::skip]
+pub const INDEX_OPTIONS: &[Opt] = &[
+    Opt::new(None, "include", "index only files matching the glob (repeatable)").value("glob"),
+    Opt::new(None, "exclude", "skip files and directories matching the glob").value("glob"),
+    Opt::new(None, "no-ignore", "don't honor .gitignore files"),
+    Opt::new(Some('h'), "help", "print this help and exit"),
+];
+
+/// The options `minigrep query` understands.
+#[rustfmt::skip]
+pub const QUERY_OPTIONS: &[Opt] = &[
+    Opt::new(None, "index", "the index file, or the directory it is in (default: .)").value("path"),
+    Opt::new(Some('h'), "help", "print this help and exit"),
+];
+
+/// The `--help` text for `minigrep index`.
+pub fn index_usage() -> String {
+    format!(
+        "Usage: hello_cargo minigrep index [options] <dir>\n\n\
+         Indexes the words of the files in <dir> into <dir>/{INDEX_FILE}, for \
+         `minigrep query`.\nRunning it again only reads the files that changed.\
+         \n\nOptions:\n{}",
+        cli::describe(INDEX_OPTIONS)
+    )
+}
+
+/// The `--help` text for `minigrep query`.
+pub fn query_usage() -> String {
+    format!(
+        "Usage: hello_cargo minigrep query [--index <path>] <query>...\n\n\
+         Prints the lines with all the words of <query>, using an index built by \
+         `minigrep index`.\nWords are matched whole and ignoring case; join \
+         alternatives with OR, and put\nphrases in double quotes: \
+         frog OR \"admiring bog\".\n\nOptions:\n{}",
+        cli::describe(QUERY_OPTIONS)
+    )
+}
+
+/// Entry point for `hello_cargo minigrep index [options] <dir>`; `args[0]` is
+/// `index`. Exits with 0, or 2 if any file couldn't be indexed.
+pub fn index_main(args: &[String]) {
+    let (dir, options) = parse_index_args(args)
+        .unwrap_or_else(|err| cli::exit_with(err, index_usage, "minigrep index"));
+
+    // A missing or unreadable old index just means starting over.
+    let mut index = Index::load(&dir.join(INDEX_FILE)).unwrap_or_else(|_| Index::new(&dir));
+    let (read, errors) = index.update(&options);
+    let saved = index.save();
+
+    for (path, e) in &errors {
+        eprintln!("{}: {e}", path.display());
+    }
+    match saved {
+        Ok(path) => {
+            let files = index.files().count();
+            println!(
+                "indexed {files} files ({read} read) into {}",
+                path.display()
+            );
+        }
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+    }
+    if !errors.is_empty() {
+        process::exit(2);
+    }
+}
+
+/// The directory and walk options for `minigrep index`.
+pub fn parse_index_args(args: &[String]) -> Result<(PathBuf, WalkOptions), ArgsError> {
+    let parsed = cli::parse(INDEX_OPTIONS, args.get(1..).unwrap_or_default())?;
+    let mut options = WalkOptions {
+        use_ignore_files: true,
+        ..WalkOptions::default()
+    };
+    for found in parsed.options {
+        let value = found.value.as_deref().unwrap_or_default();
+        let glob = || Glob::new(value).map_err(ArgsError::InvalidPattern);
+        match found.opt.long {
+            "help" => return Err(ArgsError::Help),
+            "include" => options.include.push(glob()?),
+            "exclude" => options.exclude.push(glob()?),
+            "no-ignore" => options.use_ignore_files = false,
+            other => unreachable!("no handler for --{other}"),
+        }
+    }
+    match parsed.positional.as_slice() {
+        [dir] => Ok((PathBuf::from(dir), options)),
+        [] => Err(ArgsError::MissingArgument("<dir>")),
+        [_, extra, ..] => Err(ArgsError::UnexpectedArgument(extra.clone())),
+    }
+}
+
+/// Entry point for `hello_cargo minigrep query [--index <path>] <query>...`;
+/// `args[0]` is `query`. Exits like grep: 0 when a line matched, 1 when
+/// nothing did and 2 on errors.
+pub fn query_main(args: &[String]) {
+    let (path, query) = parse_query_args(args)
+        .unwrap_or_else(|err| cli::exit_with(err, query_usage, "minigrep query"));
+
+    let result = Index::load(&path).and_then(|mut index| {
+        let errors = index.refresh();
+        let hits = index.search(&query);
+        write_hits(&hits, &mut io::stdout())?;
+        for (path, e) in &errors {
+            eprintln!("{}: {e}", path.display());
+        }
+        Ok((!hits.is_empty(), errors.is_empty()))
+    });
+    match result {
+        Ok((_, false)) => process::exit(2),
+        Ok((true, true)) => process::exit(0),
+        Ok((false, true)) => process::exit(1),
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+    }
+}
+
+/// The index file and query for `minigrep query`. The query's arguments are
+/// joined with spaces, so it doesn't have to be quoted as a whole.
+pub fn parse_query_args(args: &[String]) -> Result<(PathBuf, Query), ArgsError> {
+    let parsed = cli::parse(QUERY_OPTIONS, args.get(1..).unwrap_or_default())?;
+    let mut index = PathBuf::from(".");
+    for found in parsed.options {
+        match found.opt.long {
+            "help" => return Err(ArgsError::Help),
+            "index" => index = PathBuf::from(found.value.unwrap_or_default()),
+            other => unreachable!("no handler for --{other}"),
+        }
+    }
+    if parsed.positional.is_empty() {
+        return Err(ArgsError::MissingQuery);
+    }
+    let query = Query::parse(&parsed.positional.join(" ")).map_err(|reason| {
+        ArgsError::InvalidPattern(format!(
+            "invalid query `{}`: {reason}",
+            parsed.positional.join(" ")
+        ))
+    })?;
+    Ok((Index::file_for(&index), query))
+}
+
+// Whether `path` survives being written to an index file and read back.
+fn storable(path: &Path) -> bool {
+    path.to_str()
+        .is_some_and(|path| !path.contains(['\n', '\r']))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn words_of(text: &str) -> Vec<String> {
+        words(text).collect()
+    }
+
+    #[test]
+    fn splits_words() {
+        assert_eq!(
+            words_of("Then there's a pair_of us - don't tell!"),
+            [
+                "then", "there", "s", "a", "pair_of", "us", "don", "t", "tell"
+            ]
+        );
+    }
+
+    #[test]
+    fn parses_queries() {
+        let words = |text: &str| Query::Words(words_of(text));
+        let and = |a, b| Query::And(Box::new(a), Box::new(b));
+        let or = |a, b| Query::Or(Box::new(a), Box::new(b));
+
+        assert_eq!(Query::parse("Frog").unwrap(), words("frog"));
+        assert_eq!(
+            Query::parse("frog public").unwrap(),
+            and(words("frog"), words("public"))
+        );
+        assert_eq!(
+            Query::parse(r#"frog AND public OR "admiring bog""#).unwrap(),
+            or(and(words("frog"), words("public")), words("admiring bog"))
+        );
+        assert_eq!(Query::parse("don't").unwrap(), words("don t"));
+        assert_eq!(
+            Query::parse("and or").unwrap(),
+            and(words("and"), words("or"))
+        );
+        assert_eq!(Query::parse(r#""OR""#).unwrap(), words("or"));
+
+        for bad in [
+            "",
+            "!!",
+            "AND frog",
+            "frog OR",
+            "frog AND AND toad",
+            "a OR OR b",
+        ] {
+            assert!(Query::parse(bad).is_err(), "{bad}");
+        }
+        assert_eq!(Query::parse("\"frog").unwrap_err(), "unterminated quote");
+    }
+
+    // A scratch directory with `files`, unique to the test.
+    fn scratch(name: &str, files: &[(&str, &str)]) -> PathBuf {
+        let dir = env::temp_dir().join(format!("minigrep-index-{}-{name}", process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        for (path, text) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, text).unwrap();
+        }
+        dir
+    }
+
+    fn lines(index: &Index, query: &str) -> Vec<(String, usize)> {
+        index
+            .search(&Query::parse(query).unwrap())
+            .into_iter()
+            .map(|hit| {
+                let path = hit.path.strip_prefix(&index.root).unwrap();
+                (path.display().to_string(), hit.line)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn answers_queries_and_round_trips() {
+        let dir = scratch(
+            "queries",
+            &[
+                ("poem.txt", "How public, like a frog\nTo an admiring bog!\n"),
+                ("sub/frogs.txt", "a frog\nno\nlike a public frog\n"),
+            ],
+        );
+        let mut index = Index::new(&dir);
+        let (read, errors) = index.update(&WalkOptions::default());
+        assert_eq!((read, errors.len()), (2, 0));
+
+        assert_eq!(
+            lines(&index, "frog"),
+            [
+                (String::from("poem.txt"), 1),
+                (String::from("sub/frogs.txt"), 1),
+                (String::from("sub/frogs.txt"), 3),
+            ]
+        );
+        assert_eq!(
+            lines(&index, "PUBLIC frog"),
+            [
+                (String::from("poem.txt"), 1),
+                (String::from("sub/frogs.txt"), 3)
+            ]
+        );
+        assert_eq!(
+            lines(&index, r#""a frog" OR bog"#),
+            [
+                (String::from("poem.txt"), 1),
+                (String::from("poem.txt"), 2),
+                (String::from("sub/frogs.txt"), 1),
+            ]
+        );
+        assert_eq!(lines(&index, r#""frog a""#), []);
+
+        let mut out = Vec::new();
+        write_hits(&index.search(&Query::parse("bog").unwrap()), &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!("{}:2:To an admiring bog!\n", dir.join("poem.txt").display())
+        );
+
+        let saved = index.save().unwrap();
+        assert_eq!(saved, dir.join(INDEX_FILE));
+        let loaded = Index::load(&saved).unwrap();
+        assert_eq!(loaded, index);
+        // The index file itself is never indexed.
+        assert_eq!(index.update(&WalkOptions::default()).0, 0);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn changed_and_deleted_files_are_not_trusted() {
+        let dir = scratch("stale", &[("a.txt", "frog\n"), ("b.txt", "toad\n")]);
+        let mut index = Index::new(&dir);
+        index.update(&WalkOptions::default());
+
+        // Whatever the clock's resolution, the times differ.
+        let file = File::options().write(true).open(dir.join("a.txt")).unwrap();
+        file.set_len(0).unwrap();
+        (&file).write_all(b"newt\n").unwrap();
+        file.set_modified(UNIX_EPOCH + Duration::from_secs(1))
+            .unwrap();
+        fs::remove_file(dir.join("b.txt")).unwrap();
+        fs::write(dir.join("c.txt"), "frog\n").unwrap();
+
+        let mut refreshed = index.clone();
+        assert!(refreshed.refresh().is_empty());
+        assert_eq!(lines(&refreshed, "frog OR toad"), []);
+        assert_eq!(lines(&refreshed, "newt"), [(String::from("a.txt"), 1)]);
+
+        assert_eq!(index.update(&WalkOptions::default()).0, 2);
+        assert_eq!(lines(&index, "frog"), [(String::from("c.txt"), 1)]);
+        assert_eq!(index.files().count(), 2);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn names_that_cant_be_stored_are_reported() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = scratch(
+            "names",
+            &[("ok.txt", "frog\n"), ("line\nbreak.txt", "frog\n")],
+        );
+        fs::write(dir.join(OsStr::from_bytes(b"caf\xe9.txt")), "frog\n").unwrap();
+        let mut index = Index::new(&dir);
+        let (read, errors) = index.update(&WalkOptions::default());
+        assert_eq!((read, errors.len()), (1, 2));
+        index.save().unwrap();
+
+        // The index still reads back, and the good file isn't read again.
+        let mut index = Index::load(&dir.join(INDEX_FILE)).unwrap();
+        assert_eq!(lines(&index, "frog"), [(String::from("ok.txt"), 1)]);
+        let (read, errors) = index.update(&WalkOptions::default());
+        assert_eq!((read, errors.len()), (0, 2));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_index_files() {
+        let error = |text| Index::parse(Path::new("."), text).unwrap_err();
+        assert!(error("grep index").starts_with("not a minigrep index"));
+        assert_eq!(
+            error("minigrep-index 1\nterm frog 0:1:0:0"),
+            "line 2: posting for an unknown file"
+        );
+        assert_eq!(
+            error("minigrep-index 1\nfile 1 x a.txt"),
+            "line 2: invalid nanoseconds"
+        );
+    }
+}
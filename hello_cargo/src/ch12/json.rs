@@ -0,0 +1,152 @@
+// minigrep's `--json` output: one JSON object per line, for other programs to
+// read. A search writes a `match` record for every matching line,
+//
+//   {"type":"match","path":"poem.txt","line_number":7,"offset":142,
+//    "line":"How public, like a frog","submatches":[{"start":19,"end":23,"text":"frog"}]}
+//
+// (on one line), a `binary` record for each binary file that matches and a
+// final `summary` record with the totals. `offset` is the byte position of the
+// line in its file; submatch `start` and `end` are byte positions in `line`.
+//
+// The format is small enough to write by hand, so there's no serde dependency.
+
+use std::io::{self, Write};
+
+use super::matcher::Matcher;
+use super::output::{Printer, Summary};
+
+/// Writes each match as a JSON object on a line of its own.
+pub struct JsonPrinter<'a> {
+    out: &'a mut dyn Write,
+    matcher: &'a Matcher,
+    path: String,
+}
+
+impl<'a> JsonPrinter<'a> {
+    pub fn new(out: &'a mut dyn Write, matcher: &'a Matcher) -> JsonPrinter<'a> {
+        JsonPrinter {
+            out,
+            matcher,
+            path: String::new(),
+        }
+    }
+}
+
+// Every record names its file, so the path is used even where text output
+// would leave it out.
+impl Printer for JsonPrinter<'_> {
+    fn start_file(&mut self, path: Option<String>) {
+        self.path = path.unwrap_or_default();
+    }
+
+    fn line(&mut self, number: usize, offset: u64, text: &str, is_match: bool) -> io::Result<()> {
+        if !is_match {
+            return Ok(());
+        }
+        let submatches: Vec<String> = self
+            .matcher
+            .find_spans(text)
+            .into_iter()
+            .map(|(start, end)| {
+                format!(
+                    r#"{{"start":{start},"end":{end},"text":{}}}"#,
+                    string(&text[start..end])
+                )
+            })
+            .collect();
+        writeln!(
+            self.out,
+            r#"{{"type":"match","path":{},"line_number":{number},"offset":{offset},"line":{},"submatches":[{}]}}"#,
+            string(&self.path),
+            string(text),
+            submatches.join(",")
+        )
+    }
+
+    fn binary_file_matches(&mut self, name: &str) -> io::Result<()> {
+        writeln!(self.out, r#"{{"type":"binary","path":{}}}"#, string(name))
+    }
+}
+
+/// Writes the `summary` record that ends the output.
+pub fn write_summary(out: &mut dyn Write, summary: &Summary) -> io::Result<()> {
+    writeln!(
+        out,
+        r#"{{"type":"summary","files_searched":{},"files_matched":{},"matched_lines":{},"elapsed_ms":{:.3}}}"#,
+        summary.files_searched,
+        summary.files_matched,
+        summary.matched_lines,
+        summary.elapsed.as_secs_f64() * 1000.0
+    )
+}
+
+/// `s` as a quoted JSON string.
+pub fn string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if c < ' ' => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ch12::matcher::Mode;
+    use std::time::Duration;
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(string("plain"), r#""plain""#);
+        assert_eq!(string("a \"b\" \\ c"), r#""a \"b\" \\ c""#);
+        assert_eq!(string("tab\there\u{1}"), r#""tab\there\u0001""#);
+        assert_eq!(string("héllo"), "\"héllo\"");
+    }
+
+    #[test]
+    fn match_records() {
+        let matcher = Matcher::new("o", Mode::Substring, false).unwrap();
+        let mut out = Vec::new();
+        let mut printer = JsonPrinter::new(&mut out, &matcher);
+        printer.start_file(Some(String::from("a \"quoted\" name")));
+        printer.line(1, 0, "skipped", false).unwrap();
+        printer.line(2, 8, "foo", true).unwrap();
+        printer.binary_file_matches("data.bin").unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            concat!(
+                r#"{"type":"match","path":"a \"quoted\" name","line_number":2,"offset":8,"line":"foo","#,
+                r#""submatches":[{"start":1,"end":2,"text":"o"},{"start":2,"end":3,"text":"o"}]}"#,
+                "\n",
+                r#"{"type":"binary","path":"data.bin"}"#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn summary_record() {
+        let summary = Summary {
+            files_searched: 3,
+            files_matched: 2,
+            matched_lines: 5,
+            elapsed: Duration::from_micros(1500),
+        };
+        let mut out = Vec::new();
+        write_summary(&mut out, &summary).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "{\"type\":\"summary\",\"files_searched\":3,\"files_matched\":2,\"matched_lines\":5,\"elapsed_ms\":1.500}\n"
+        );
+    }
+}
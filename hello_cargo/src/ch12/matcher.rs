@@ -0,0 +1,320 @@
+// How minigrep decides whether a line matches, and where.
+//
+// The query is compiled once, when the Config is built, into a Matcher that
+// the search then reuses for every line.
+
+use regex::{Regex, RegexBuilder};
+
+use super::ch12::fold_case;
+use super::fuzzy;
+
+/// How the query is interpreted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// The query is a plain substring (the default, `-F`).
+    Substring,
+    /// The query must match whole words (`-w`).
+    WholeWord,
+    /// The query is a regular expression (`-E`).
+    Regex,
+    /// The query's characters must appear in order, with anything between
+    /// them (`--fuzzy`); see `fuzzy`.
+    Fuzzy,
+}
+
+/// A compiled query.
+#[derive(Debug, Clone)]
+pub enum Matcher {
+    Literal { query: String },
+    // The folded query is kept so each line only needs folding once.
+    LiteralIgnoreCase { folded: String },
+    Pattern(Regex),
+    Fuzzy { query: Vec<char>, ignore_case: bool },
+}
+
+impl Matcher {
+    /// Compiles `query` for `mode`; fails only if a regular expression is invalid.
+    ///
+    /// Whole-word and regex matching ignore case with the regex crate's simple
+    /// Unicode case folding, so unlike substring mode `ß` doesn't match `ss`.
+    /// Fuzzy matching ignores case unless asked not to or the query has an
+    /// upper-case letter, like fzf's "smart case".
+    pub fn new(query: &str, mode: Mode, ignore_case: bool) -> Result<Matcher, String> {
+        let pattern = match mode {
+            Mode::Substring if ignore_case => {
+                return Ok(Matcher::LiteralIgnoreCase {
+                    folded: fold_case(query),
+                });
+            }
+            Mode::Substring => {
+                return Ok(Matcher::Literal {
+                    query: query.to_string(),
+                });
+            }
+            Mode::WholeWord => format!(r"\b(?:{})\b", regex::escape(query)),
+            Mode::Regex => query.to_string(),
+            Mode::Fuzzy => {
+                return Ok(Matcher::Fuzzy {
+                    query: query.chars().collect(),
+                    ignore_case: ignore_case || !query.chars().any(char::is_uppercase),
+                });
+            }
+        };
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(ignore_case)
+            .build()
+            .map(Matcher::Pattern)
+            .map_err(|e| format!("invalid regular expression `{query}`:\n{e}"))
+    }
+
+    pub fn is_match(&self, line: &str) -> bool {
+        match self {
+            Matcher::Literal { query } => line.contains(query.as_str()),
+            Matcher::LiteralIgnoreCase { folded } => fold_case(line).contains(folded.as_str()),
+            Matcher::Pattern(regex) => regex.is_match(line),
+            Matcher::Fuzzy { query, ignore_case } => fuzzy::is_match(query, line, *ignore_case),
+        }
+    }
+
+    /// How well `line` matches, for ranking, in fuzzy mode only.
+    pub fn score(&self, line: &str) -> Option<i64> {
+        match self {
+            Matcher::Fuzzy { query, ignore_case } => {
+                fuzzy::best_match(query, line, *ignore_case).map(|found| found.score)
+            }
+            _ => None,
+        }
+    }
+
+    /// Byte ranges of the non-empty, non-overlapping matches in `line`.
+    pub fn find_spans(&self, line: &str) -> Vec<(usize, usize)> {
+        match self {
+            Matcher::Literal { query } if query.is_empty() => Vec::new(),
+            Matcher::Literal { query } => line
+                .match_indices(query.as_str())
+                .map(|(start, found)| (start, start + found.len()))
+                .collect(),
+            Matcher::LiteralIgnoreCase { folded } if folded.is_empty() => Vec::new(),
+            // A character that folds to several, like `ß` to `ss`, can hold
+            // more than one match, or end one and start the next; those
+            // matches share its span, so overlapping spans are merged.
+            Matcher::LiteralIgnoreCase { folded } => {
+                let (folded_line, origins) = fold_with_origins(line);
+                let mut spans: Vec<(usize, usize)> = Vec::new();
+                for (start, found) in folded_line.match_indices(folded.as_str()) {
+                    let (start, end) = (origins[start].0, origins[start + found.len() - 1].1);
+                    match spans.last_mut() {
+                        Some(last) if start < last.1 => last.1 = last.1.max(end),
+                        _ => spans.push((start, end)),
+                    }
+                }
+                spans
+            }
+            Matcher::Pattern(regex) => regex
+                .find_iter(line)
+                .filter(|m| !m.is_empty())
+                .map(|m| (m.start(), m.end()))
+                .collect(),
+            // Each run of adjacent matched characters is one span.
+            Matcher::Fuzzy { query, ignore_case } => {
+                let Some(found) = fuzzy::best_match(query, line, *ignore_case) else {
+                    return Vec::new();
+                };
+                let mut spans: Vec<(usize, usize)> = Vec::new();
+                for start in found.positions {
+                    let end = start + line[start..].chars().next().map_or(0, char::len_utf8);
+                    match spans.last_mut() {
+                        Some(last) if last.1 == start => last.1 = end,
+                        _ => spans.push((start, end)),
+                    }
+                }
+                spans
+            }
+        }
+    }
+
+    /// `line` with every match replaced by `replacement`, and the byte ranges
+    /// of the replacements in the result.
+    ///
+    /// For regular expressions, and whole words, `replacement` can refer to
+    /// capture groups as `$1` or `${name}`, with `$0` for the whole match and
+    /// `$$` for a `$`. For substrings it is inserted as is.
+    pub fn replace(&self, line: &str, replacement: &str) -> (String, Vec<(usize, usize)>) {
+        let mut replaced = String::with_capacity(line.len());
+        let mut spans = Vec::new();
+        let mut end_of_last = 0;
+
+        if let Matcher::Pattern(regex) = self {
+            for captures in regex.captures_iter(line) {
+                let found = captures.get(0).unwrap();
+                // Empty matches aren't highlighted either, see `find_spans`.
+                if found.is_empty() {
+                    continue;
+                }
+                replaced.push_str(&line[end_of_last..found.start()]);
+                let start = replaced.len();
+                captures.expand(replacement, &mut replaced);
+                spans.push((start, replaced.len()));
+                end_of_last = found.end();
+            }
+        } else {
+            for (start, end) in self.find_spans(line) {
+                replaced.push_str(&line[end_of_last..start]);
+                spans.push((replaced.len(), replaced.len() + replacement.len()));
+                replaced.push_str(replacement);
+                end_of_last = end;
+            }
+        }
+        replaced.push_str(&line[end_of_last..]);
+        (replaced, spans)
+    }
+}
+
+// Folds `line` like `fold_case`, also returning, for every byte of the folded
+// text, the byte range of the original character it came from.
+fn fold_with_origins(line: &str) -> (String, Vec<(usize, usize)>) {
+    let mut folded = String::new();
+    let mut origins = Vec::new();
+    for (start, c) in line.char_indices() {
+        let end = start + c.len_utf8();
+        for f in c.to_uppercase().flat_map(char::to_lowercase) {
+            folded.push(f);
+            origins.extend(std::iter::repeat_n((start, end), f.len_utf8()));
+        }
+    }
+    (folded, origins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(query: &str, mode: Mode, ignore_case: bool, line: &str) -> Vec<(usize, usize)> {
+        Matcher::new(query, mode, ignore_case)
+            .unwrap()
+            .find_spans(line)
+    }
+
+    #[test]
+    fn substring_spans() {
+        assert_eq!(
+            spans("o", Mode::Substring, false, "How public, like a frog"),
+            [(1, 2), (21, 22)]
+        );
+    }
+
+    #[test]
+    fn ignore_case_spans_point_into_the_original_line() {
+        let line = "Große Straße";
+        let found = spans("SS", Mode::Substring, true, line);
+        assert_eq!(
+            found.iter().map(|&(s, e)| &line[s..e]).collect::<Vec<_>>(),
+            ["ß", "ß"]
+        );
+        // Both `s`es of a `ß` match, and `ss` can straddle `ßs`.
+        assert_eq!(
+            spans("s", Mode::Substring, true, "Straße"),
+            [(0, 1), (4, 6)]
+        );
+        assert_eq!(spans("ss", Mode::Substring, true, "sßs"), [(0, 4)]);
+        assert_eq!(
+            Matcher::new("s", Mode::Substring, true)
+                .unwrap()
+                .replace("Straße", "z"),
+            (String::from("ztraze"), vec![(0, 1), (4, 5)])
+        );
+    }
+
+    #[test]
+    fn whole_word() {
+        let matcher = Matcher::new("us", Mode::WholeWord, false).unwrap();
+        assert!(matcher.is_match("Then there's a pair of us - don't tell!"));
+        assert!(!matcher.is_match("How public, like a frog"));
+    }
+
+    #[test]
+    fn whole_word_escapes_the_query() {
+        let matcher = Matcher::new("a.b", Mode::WholeWord, false).unwrap();
+        assert!(matcher.is_match("x a.b y"));
+        assert!(!matcher.is_match("x acb y"));
+    }
+
+    #[test]
+    fn regex_mode() {
+        let matcher = Matcher::new(r"^How \w+", Mode::Regex, false).unwrap();
+        assert!(matcher.is_match("How dreary to be somebody!"));
+        assert_eq!(matcher.find_spans("How public, like a frog"), [(0, 10)]);
+        assert!(
+            Matcher::new("^how", Mode::Regex, true)
+                .unwrap()
+                .is_match("How")
+        );
+    }
+
+    #[test]
+    fn replacements() {
+        let replace = |query, mode, ignore_case, replacement| {
+            Matcher::new(query, mode, ignore_case)
+                .unwrap()
+                .replace("How public, like a frog", replacement)
+        };
+        assert_eq!(
+            replace("frog", Mode::Substring, false, "$1 toad"),
+            (String::from("How public, like a $1 toad"), vec![(19, 26)])
+        );
+        assert_eq!(
+            replace("HOW", Mode::Substring, true, "So"),
+            (String::from("So public, like a frog"), vec![(0, 2)])
+        );
+        assert_eq!(
+            replace(r"(\w+), like a (\w+)", Mode::Regex, false, "$2, like a $1").0,
+            "How frog, like a public"
+        );
+        assert_eq!(
+            replace(r"(?P<first>\w)(\w*)", Mode::Regex, false, "${2}${first}ay").0,
+            "owHay ublicpay, ikelay aay rogfay"
+        );
+        assert_eq!(
+            replace("like", Mode::WholeWord, false, "[$0] $$").0,
+            "How public, [like] $ a frog"
+        );
+        // Only the non-empty matches of `o*` are replaced.
+        assert_eq!(
+            replace("o*", Mode::Regex, false, "0"),
+            (
+                String::from("H0w public, like a fr0g"),
+                vec![(1, 2), (21, 22)]
+            )
+        );
+    }
+
+    #[test]
+    fn fuzzy_mode() {
+        let matcher = Matcher::new("hpub", Mode::Fuzzy, false).unwrap();
+        assert!(matcher.is_match("How public, like a frog"));
+        assert_eq!(
+            matcher.find_spans("How public, like a frog"),
+            [(0, 1), (4, 7)]
+        );
+        assert!(matcher.score("How public").unwrap() > matcher.score("ship pub").unwrap());
+        assert_eq!(matcher.score("nothing"), None);
+
+        let matcher = Matcher::new("How", Mode::Fuzzy, false).unwrap();
+        assert!(!matcher.is_match("how"));
+        assert!(
+            Matcher::new("How", Mode::Fuzzy, true)
+                .unwrap()
+                .is_match("how")
+        );
+    }
+
+    #[test]
+    fn invalid_regex_is_an_error() {
+        let err = Matcher::new("(unclosed", Mode::Regex, false).unwrap_err();
+        assert!(
+            err.starts_with("invalid regular expression `(unclosed`"),
+            "{err}"
+        );
+    }
+}
@@ -1 +1,73 @@
-pub mod ch12;
\ No newline at end of file
+#![allow(clippy::module_inception)]
+
+pub mod ch12;
+pub mod fuzzy;
+pub mod glob;
+pub mod index;
+pub mod json;
+pub mod matcher;
+pub mod output;
+pub mod parallel;
+pub mod replace;
+pub mod settings;
+pub mod walk;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch12;
+
+impl Chapter for Ch12 {
+    fn id(&self) -> &'static str {
+        "ch12"
+    }
+
+    fn title(&self) -> &'static str {
+        "An I/O Project: minigrep"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "command line arguments",
+            "reading files",
+            "Config",
+            "error handling",
+            "search",
+            "exit codes",
+            "environment variables",
+            "case-insensitive",
+            "regex",
+            "directories",
+            "glob",
+            "context",
+            "ANSI colors",
+        ]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "$ minigrep nobody poem.txt")?;
+        search(&["minigrep", "nobody", ch12::POEM], out)?;
+        writeln!(out, "$ minigrep -i the poem.txt")?;
+        search(&["minigrep", "-i", "the", ch12::POEM], out)?;
+        writeln!(out, "$ minigrep -w us poem.txt")?;
+        search(&["minigrep", "-w", "us", ch12::POEM], out)?;
+        writeln!(out, "$ minigrep -E '^How \\w+' poem.txt")?;
+        search(&["minigrep", "-E", r"^How \w+", ch12::POEM], out)?;
+        writeln!(out, "$ minigrep -C 1 frog poem.txt")?;
+        search(&["minigrep", "-C", "1", "frog", ch12::POEM], out)
+    }
+}
+
+// Runs minigrep on `args` the way the command would, except that IGNORE_CASE
+// is ignored so the demo's output doesn't depend on the environment.
+fn search(args: &[&str], out: &mut dyn Write) -> io::Result<()> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    let config = ch12::Config::with_env(&args, false).map_err(io::Error::other)?;
+    // Whether anything matched only matters for the exit code of the real command.
+    ch12::run(config, out)
+        .map(|_matched| ())
+        .map_err(|e| io::Error::other(e.to_string()))
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch12];
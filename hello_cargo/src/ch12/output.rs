@@ -0,0 +1,623 @@
+// Writes minigrep's results the way grep does:
+//
+//   path:12:matching line      (`:` after a matching line's number)
+//   path-13-context line       (`-` after a context line's number)
+//   --                         (between groups of lines that aren't adjacent)
+//
+// Lines are fed in one at a time, so only the last `before` lines are kept
+// around for before-context. Ranked output (`--fuzzy`) is the exception: it
+// has to see every match before printing the best one first.
+
+use std::collections::VecDeque;
+use std::io::{self, IsTerminal, Write};
+use std::time::Duration;
+
+use super::matcher::Matcher;
+
+/// When to color the output.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorChoice {
+    Always,
+    Never,
+    /// Color only when writing to a terminal.
+    Auto,
+}
+
+impl ColorChoice {
+    /// Parses a `--color` value: `always`, `never` or `auto`.
+    pub fn parse(value: &str) -> Option<ColorChoice> {
+        match value {
+            "always" => Some(ColorChoice::Always),
+            "never" => Some(ColorChoice::Never),
+            "auto" => Some(ColorChoice::Auto),
+            _ => None,
+        }
+    }
+
+    /// Turns `Auto` into `Always` or `Never` depending on whether stdout is a terminal.
+    pub fn for_stdout(self) -> ColorChoice {
+        match self {
+            ColorChoice::Auto if io::stdout().is_terminal() => ColorChoice::Always,
+            ColorChoice::Auto => ColorChoice::Never,
+            choice => choice,
+        }
+    }
+}
+
+/// How results are written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Lines prefixed with their number, like grep.
+    Text,
+    /// One JSON object per match, see `json::JsonPrinter`.
+    Json,
+    /// The number of matching lines in each file (`-c`).
+    Count,
+    /// Only the names of files with a match (`-l`).
+    FilesWithMatches,
+    /// Only the names of files without a match (`-L`).
+    FilesWithoutMatch,
+}
+
+impl Format {
+    /// Whether every result names its file, even when only one is searched.
+    pub fn always_shows_paths(self) -> bool {
+        matches!(
+            self,
+            Format::Json | Format::FilesWithMatches | Format::FilesWithoutMatch
+        )
+    }
+}
+
+/// Receives the lines of each searched file, in order, and writes the results.
+pub trait Printer {
+    /// Starts a new file; `path` is the name to show for it, if any.
+    fn start_file(&mut self, path: Option<String>);
+
+    /// Feeds the next line of the current file. `number` counts from 1 and
+    /// `offset` is the position of the line's first byte in the file.
+    fn line(&mut self, number: usize, offset: u64, text: &str, is_match: bool) -> io::Result<()>;
+
+    /// Reports a match in a binary file, whose lines aren't printed.
+    fn binary_file_matches(&mut self, name: &str) -> io::Result<()>;
+
+    /// Ends the current file, after its last line or once the search stops.
+    fn finish_file(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Ends the whole search, after the last file.
+    fn finish(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Totals for a whole search.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Summary {
+    /// Files that were read to the end, or to their first match if binary.
+    pub files_searched: usize,
+    pub files_matched: usize,
+    pub matched_lines: usize,
+    pub elapsed: Duration,
+}
+
+impl Summary {
+    /// Counts a searched file with `matched_lines` matches.
+    pub fn add_file(&mut self, matched_lines: usize) {
+        self.files_searched += 1;
+        if matched_lines > 0 {
+            self.files_matched += 1;
+        }
+        self.matched_lines += matched_lines;
+    }
+}
+
+// ANSI escape sequences, with grep's default colors.
+const PATH: &str = "\x1b[35m";
+const LINE_NUMBER: &str = "\x1b[32m";
+const SEPARATOR: &str = "\x1b[36m";
+const MATCH: &str = "\x1b[1;31m";
+const RESET: &str = "\x1b[0m";
+
+/// Writes the `--` line that separates groups of context.
+///
+/// `LinePrinter` adds these itself; this is for joining the output of
+/// printers that each saw only some of the files.
+pub fn write_separator(out: &mut dyn Write, color: bool) -> io::Result<()> {
+    if color {
+        writeln!(out, "{SEPARATOR}--{RESET}")
+    } else {
+        writeln!(out, "--")
+    }
+}
+
+/// Prints matching lines with their context.
+pub struct LinePrinter<'a> {
+    out: &'a mut dyn Write,
+    matcher: &'a Matcher,
+    before: usize,
+    after: usize,
+    color: bool,
+    path: Option<String>,
+    // The most recent lines that weren't printed, for before-context.
+    recent: VecDeque<(usize, String)>,
+    // How many more lines to print as after-context.
+    after_left: usize,
+    // Number of the last line printed from the current file.
+    last_printed: Option<usize>,
+    // Whether anything has been printed yet, in any file.
+    printed_any: bool,
+    // What to print instead of each match, if anything.
+    replacement: Option<&'a str>,
+}
+
+impl<'a> LinePrinter<'a> {
+    pub fn new(
+        out: &'a mut dyn Write,
+        matcher: &'a Matcher,
+        before: usize,
+        after: usize,
+        color: bool,
+    ) -> LinePrinter<'a> {
+        LinePrinter {
+            out,
+            matcher,
+            before,
+            after,
+            color,
+            path: None,
+            recent: VecDeque::new(),
+            after_left: 0,
+            last_printed: None,
+            printed_any: false,
+            replacement: None,
+        }
+    }
+
+    /// Prints matching lines with their matches replaced, as by `Matcher::replace`.
+    pub fn replacing(mut self, replacement: Option<&'a str>) -> LinePrinter<'a> {
+        self.replacement = replacement;
+        self
+    }
+
+    fn print(&mut self, number: usize, text: &str, is_match: bool) -> io::Result<()> {
+        let has_context = self.before > 0 || self.after > 0;
+        let adjacent = self.last_printed.is_some_and(|last| last + 1 == number);
+        if has_context && self.printed_any && !adjacent {
+            self.separator_line()?;
+        }
+        self.last_printed = Some(number);
+        self.printed_any = true;
+
+        let separator = if is_match { ":" } else { "-" };
+        write_prefix(
+            self.out,
+            self.color,
+            self.path.as_deref(),
+            number,
+            separator,
+        )?;
+
+        if !is_match {
+            return writeln!(self.out, "{text}");
+        }
+        let (text, spans) = match self.replacement {
+            Some(replacement) => self.matcher.replace(text, replacement),
+            None if self.color => (text.to_string(), self.matcher.find_spans(text)),
+            None => return writeln!(self.out, "{text}"),
+        };
+        write_highlighted(self.out, self.color, &text, &spans)
+    }
+
+    fn separator_line(&mut self) -> io::Result<()> {
+        write_separator(self.out, self.color)
+    }
+}
+
+// Writes `path:12:` before a line, or `path-12-` with a `-` separator.
+fn write_prefix(
+    out: &mut dyn Write,
+    color: bool,
+    path: Option<&str>,
+    number: usize,
+    separator: &str,
+) -> io::Result<()> {
+    if let Some(path) = path {
+        write_colored(out, color, PATH, path)?;
+        write_colored(out, color, SEPARATOR, separator)?;
+    }
+    write_colored(out, color, LINE_NUMBER, &number.to_string())?;
+    write_colored(out, color, SEPARATOR, separator)
+}
+
+// Writes `text` and a newline, with the byte ranges in `spans` highlighted if
+// `color` is on.
+fn write_highlighted(
+    out: &mut dyn Write,
+    color: bool,
+    text: &str,
+    spans: &[(usize, usize)],
+) -> io::Result<()> {
+    if !color {
+        return writeln!(out, "{text}");
+    }
+    let mut end_of_last = 0;
+    for &(start, end) in spans {
+        write!(
+            out,
+            "{}{MATCH}{}{RESET}",
+            &text[end_of_last..start],
+            &text[start..end]
+        )?;
+        end_of_last = end;
+    }
+    writeln!(out, "{}", &text[end_of_last..])
+}
+
+fn write_colored(out: &mut dyn Write, enabled: bool, color: &str, text: &str) -> io::Result<()> {
+    if enabled {
+        write!(out, "{color}{text}{RESET}")
+    } else {
+        write!(out, "{text}")
+    }
+}
+
+// The path, if any, is the prefix for each line.
+impl Printer for LinePrinter<'_> {
+    fn start_file(&mut self, path: Option<String>) {
+        self.path = path;
+        self.recent.clear();
+        self.after_left = 0;
+        self.last_printed = None;
+    }
+
+    fn line(&mut self, number: usize, _offset: u64, text: &str, is_match: bool) -> io::Result<()> {
+        if is_match {
+            while let Some((number, text)) = self.recent.pop_front() {
+                self.print(number, &text, false)?;
+            }
+            self.print(number, text, true)?;
+            self.after_left = self.after;
+        } else if self.after_left > 0 {
+            self.after_left -= 1;
+            self.print(number, text, false)?;
+        } else if self.before > 0 {
+            if self.recent.len() == self.before {
+                self.recent.pop_front();
+            }
+            self.recent.push_back((number, text.to_string()));
+        }
+        Ok(())
+    }
+
+    fn binary_file_matches(&mut self, name: &str) -> io::Result<()> {
+        writeln!(self.out, "Binary file {name} matches")
+    }
+}
+
+/// Prints the matching lines of all files together, best score first, as
+/// given by `Matcher::score`. Lines that score the same keep their order.
+pub struct RankedPrinter<'a> {
+    out: &'a mut dyn Write,
+    matcher: &'a Matcher,
+    color: bool,
+    path: Option<String>,
+    // (score, path, line number, line) for each match so far.
+    matches: Vec<(i64, Option<String>, usize, String)>,
+}
+
+impl<'a> RankedPrinter<'a> {
+    pub fn new(out: &'a mut dyn Write, matcher: &'a Matcher, color: bool) -> RankedPrinter<'a> {
+        RankedPrinter {
+            out,
+            matcher,
+            color,
+            path: None,
+            matches: Vec::new(),
+        }
+    }
+}
+
+impl Printer for RankedPrinter<'_> {
+    fn start_file(&mut self, path: Option<String>) {
+        self.path = path;
+    }
+
+    fn line(&mut self, number: usize, _offset: u64, text: &str, is_match: bool) -> io::Result<()> {
+        if is_match {
+            let score = self.matcher.score(text).unwrap_or(0);
+            self.matches
+                .push((score, self.path.clone(), number, text.to_string()));
+        }
+        Ok(())
+    }
+
+    // Binary files have no lines to rank, so they are reported right away.
+    fn binary_file_matches(&mut self, name: &str) -> io::Result<()> {
+        writeln!(self.out, "Binary file {name} matches")
+    }
+
+    fn finish(&mut self) -> io::Result<()> {
+        self.matches
+            .sort_by_key(|&(score, ..)| std::cmp::Reverse(score));
+        for (_, path, number, text) in self.matches.drain(..) {
+            write_prefix(self.out, self.color, path.as_deref(), number, ":")?;
+            let spans = if self.color {
+                self.matcher.find_spans(&text)
+            } else {
+                Vec::new()
+            };
+            write_highlighted(self.out, self.color, &text, &spans)?;
+        }
+        Ok(())
+    }
+}
+
+/// Prints how many lines of each file matched, after its path if it has one.
+pub struct CountPrinter<'a> {
+    out: &'a mut dyn Write,
+    color: bool,
+    path: Option<String>,
+    count: usize,
+}
+
+impl<'a> CountPrinter<'a> {
+    pub fn new(out: &'a mut dyn Write, color: bool) -> CountPrinter<'a> {
+        CountPrinter {
+            out,
+            color,
+            path: None,
+            count: 0,
+        }
+    }
+}
+
+impl Printer for CountPrinter<'_> {
+    fn start_file(&mut self, path: Option<String>) {
+        self.path = path;
+        self.count = 0;
+    }
+
+    fn line(
+        &mut self,
+        _number: usize,
+        _offset: u64,
+        _text: &str,
+        is_match: bool,
+    ) -> io::Result<()> {
+        if is_match {
+            self.count += 1;
+        }
+        Ok(())
+    }
+
+    fn binary_file_matches(&mut self, _name: &str) -> io::Result<()> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish_file(&mut self) -> io::Result<()> {
+        if let Some(path) = &self.path {
+            write_colored(self.out, self.color, PATH, path)?;
+            write_colored(self.out, self.color, SEPARATOR, ":")?;
+        }
+        writeln!(self.out, "{}", self.count)
+    }
+}
+
+/// Prints the path of each file that has a match, or of each that doesn't.
+pub struct FileListPrinter<'a> {
+    out: &'a mut dyn Write,
+    color: bool,
+    with_matches: bool,
+    path: String,
+    matched: bool,
+}
+
+impl<'a> FileListPrinter<'a> {
+    /// Lists the files with a match if `with_matches`, the others if not.
+    pub fn new(out: &'a mut dyn Write, color: bool, with_matches: bool) -> FileListPrinter<'a> {
+        FileListPrinter {
+            out,
+            color,
+            with_matches,
+            path: String::new(),
+            matched: false,
+        }
+    }
+}
+
+impl Printer for FileListPrinter<'_> {
+    fn start_file(&mut self, path: Option<String>) {
+        self.path = path.unwrap_or_default();
+        self.matched = false;
+    }
+
+    fn line(
+        &mut self,
+        _number: usize,
+        _offset: u64,
+        _text: &str,
+        is_match: bool,
+    ) -> io::Result<()> {
+        self.matched |= is_match;
+        Ok(())
+    }
+
+    fn binary_file_matches(&mut self, _name: &str) -> io::Result<()> {
+        self.matched = true;
+        Ok(())
+    }
+
+    fn finish_file(&mut self) -> io::Result<()> {
+        if self.matched == self.with_matches {
+            write_colored(self.out, self.color, PATH, &self.path)?;
+            writeln!(self.out)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ch12::matcher::Mode;
+
+    fn print(query: &str, before: usize, after: usize, color: bool, text: &str) -> String {
+        let matcher = Matcher::new(query, Mode::Substring, false).unwrap();
+        let mut out = Vec::new();
+        let mut printer = LinePrinter::new(&mut out, &matcher, before, after, color);
+        for (i, line) in text.lines().enumerate() {
+            printer
+                .line(i + 1, 0, line, matcher.is_match(line))
+                .unwrap();
+        }
+        String::from_utf8(out).unwrap()
+    }
+
+    const TEXT: &str = "a\nb\nmatch 1\nc\nd\ne\nf\nmatch 2\nmatch 3\ng";
+
+    #[test]
+    fn without_context() {
+        assert_eq!(
+            print("match", 0, 0, false, TEXT),
+            "3:match 1\n8:match 2\n9:match 3\n"
+        );
+    }
+
+    #[test]
+    fn before_and_after_context_with_separators() {
+        assert_eq!(
+            print("match", 1, 1, false, TEXT),
+            "2-b\n3:match 1\n4-c\n--\n7-f\n8:match 2\n9:match 3\n10-g\n"
+        );
+    }
+
+    #[test]
+    fn overlapping_context_is_merged() {
+        assert_eq!(
+            print("match", 2, 2, false, TEXT),
+            "1-a\n2-b\n3:match 1\n4-c\n5-d\n6-e\n7-f\n8:match 2\n9:match 3\n10-g\n"
+        );
+    }
+
+    #[test]
+    fn highlights_matches() {
+        assert_eq!(
+            print("at", 0, 0, true, "a cat sat"),
+            "\x1b[32m1\x1b[0m\x1b[36m:\x1b[0ma c\x1b[1;31mat\x1b[0m s\x1b[1;31mat\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn replaces_matches() {
+        let matcher = Matcher::new("at", Mode::Substring, false).unwrap();
+        let mut out = Vec::new();
+        let mut printer = LinePrinter::new(&mut out, &matcher, 0, 1, true).replacing(Some("og"));
+        printer.line(1, 0, "a cat sat", true).unwrap();
+        printer.line(2, 10, "a cat sat", false).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[32m1\x1b[0m\x1b[36m:\x1b[0ma c\x1b[1;31mog\x1b[0m s\x1b[1;31mog\x1b[0m\n\
+             \x1b[32m2\x1b[0m\x1b[36m-\x1b[0ma cat sat\n"
+        );
+    }
+
+    #[test]
+    fn file_prefixes() {
+        let matcher = Matcher::new("x", Mode::Substring, false).unwrap();
+        let mut out = Vec::new();
+        let mut printer = LinePrinter::new(&mut out, &matcher, 0, 1, false);
+        for path in ["one.txt", "two.txt"] {
+            printer.start_file(Some(path.to_string()));
+            printer.line(1, 0, "x", true).unwrap();
+            printer.line(2, 2, "y", false).unwrap();
+        }
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "one.txt:1:x\none.txt-2-y\n--\ntwo.txt:1:x\ntwo.txt-2-y\n"
+        );
+    }
+
+    #[test]
+    fn ranks_matches_across_files() {
+        let matcher = Matcher::new("pub", Mode::Fuzzy, false).unwrap();
+        let mut out = Vec::new();
+        let mut printer = RankedPrinter::new(&mut out, &matcher, false);
+        for (path, lines) in [
+            ("a.txt", ["p u b", "no", "a pub"]),
+            ("b.txt", ["public", "rpub", "p u b"]),
+        ] {
+            printer.start_file(Some(path.to_string()));
+            for (i, line) in lines.iter().enumerate() {
+                printer
+                    .line(i + 1, 0, line, matcher.is_match(line))
+                    .unwrap();
+            }
+            printer.finish_file().unwrap();
+        }
+        printer.finish().unwrap();
+        drop(printer);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a.txt:3:a pub\nb.txt:1:public\na.txt:1:p u b\nb.txt:3:p u b\nb.txt:2:rpub\n"
+        );
+
+        let mut out = Vec::new();
+        let mut printer = RankedPrinter::new(&mut out, &matcher, true);
+        printer.start_file(None);
+        printer.line(1, 0, "a pub", true).unwrap();
+        printer.finish().unwrap();
+        drop(printer);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\x1b[32m1\x1b[0m\x1b[36m:\x1b[0ma \x1b[1;31mpub\x1b[0m\n"
+        );
+    }
+
+    // Feeds each file's lines, `true` for a match, to `printer`.
+    fn feed(printer: &mut dyn Printer, files: &[(&str, &[bool])]) {
+        for (path, lines) in files {
+            printer.start_file(Some(path.to_string()));
+            for (i, &is_match) in lines.iter().enumerate() {
+                printer.line(i + 1, 0, "", is_match).unwrap();
+            }
+            printer.finish_file().unwrap();
+        }
+    }
+
+    const FILES: &[(&str, &[bool])] = &[
+        ("a.txt", &[true, false, true]),
+        ("b.txt", &[false]),
+        ("c.txt", &[false, true]),
+    ];
+
+    #[test]
+    fn counts() {
+        let mut out = Vec::new();
+        feed(&mut CountPrinter::new(&mut out, false), FILES);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "a.txt:2\nb.txt:0\nc.txt:1\n"
+        );
+
+        let mut out = Vec::new();
+        let mut printer = CountPrinter::new(&mut out, false);
+        printer.start_file(None);
+        printer.line(1, 0, "", true).unwrap();
+        printer.finish_file().unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "1\n");
+    }
+
+    #[test]
+    fn file_lists() {
+        let mut out = Vec::new();
+        feed(&mut FileListPrinter::new(&mut out, false, true), FILES);
+        assert_eq!(String::from_utf8(out).unwrap(), "a.txt\nc.txt\n");
+
+        let mut out = Vec::new();
+        feed(&mut FileListPrinter::new(&mut out, false, false), FILES);
+        assert_eq!(String::from_utf8(out).unwrap(), "b.txt\n");
+    }
+}
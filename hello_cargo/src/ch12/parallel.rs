@@ -0,0 +1,187 @@
+// Fans work out to a fixed number of threads and collects the results in the
+// original order, using ch16's multi-producer channel pattern: every worker
+// gets a clone of one Sender, and the calling thread reads the Receiver.
+//
+// Results that finish early wait in memory for the ones before them, so the
+// calling thread only hands out a few items past the next one to emit. A slow
+// item then holds up the workers instead of letting results pile up.
+
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+
+use crate::ch16::ch16::fan_in;
+
+/// Why `map_ordered` didn't get through every item.
+#[derive(Debug, PartialEq)]
+pub enum MapError<E> {
+    /// `emit` failed.
+    Emit(E),
+    /// `work` panicked, so an item has no result; the results after it are
+    /// never emitted.
+    WorkerPanicked,
+}
+
+/// How many items, per worker, can be handed out past the next result to emit.
+pub const ITEMS_AHEAD_PER_JOB: usize = 2;
+
+/// Runs `work` on every item on `jobs` worker threads and passes the results
+/// to `emit` in the order of `items`, each as soon as all earlier ones are done.
+///
+/// At most `jobs * ITEMS_AHEAD_PER_JOB` items are being worked on or waiting
+/// to be emitted at any time, so that many results is all that's ever held.
+///
+/// Stops at the first error from `emit`; workers notice the closed channel and
+/// finish after their current item.
+pub fn map_ordered<T, R, E, W>(
+    items: Vec<T>,
+    jobs: usize,
+    work: W,
+    mut emit: impl FnMut(R) -> Result<(), E>,
+) -> Result<(), MapError<E>>
+where
+    T: Send + 'static,
+    R: Send + 'static,
+    W: Fn(T) -> R + Send + Sync + 'static,
+{
+    let jobs = jobs.max(1);
+    let total = items.len();
+    let (queue, queued) = mpsc::channel::<(usize, T)>();
+    let queued = Arc::new(Mutex::new(queued));
+    let work = Arc::new(work);
+
+    let workers = (0..jobs).map(|_| {
+        let queued = Arc::clone(&queued);
+        let work = Arc::clone(&work);
+        move |tx: Sender<(usize, Option<R>)>| loop {
+            // The lock is only held while taking the next item.
+            let Ok((index, item)) = queued.lock().unwrap().recv() else {
+                break;
+            };
+            // A panic is sent on as a missing result, or the calling thread
+            // would wait for it forever.
+            let result = panic::catch_unwind(AssertUnwindSafe(|| work(item))).ok();
+            if tx.send((index, result)).is_err() {
+                break;
+            }
+        }
+    });
+    let rx = fan_in(workers);
+
+    // `queued` is still held here, so sending can't fail.
+    let mut items = items.into_iter().enumerate();
+    for item in items.by_ref().take(jobs * ITEMS_AHEAD_PER_JOB) {
+        queue.send(item).unwrap();
+    }
+
+    // Results that arrived before an earlier one, waiting for their turn.
+    let mut waiting = BTreeMap::new();
+    let mut next = 0;
+    while next < total {
+        let Ok((index, Some(result))) = rx.recv() else {
+            return Err(MapError::WorkerPanicked);
+        };
+        waiting.insert(index, result);
+        while let Some(result) = waiting.remove(&next) {
+            emit(result).map_err(MapError::Emit)?;
+            next += 1;
+            if let Some(item) = items.next() {
+                queue.send(item).unwrap();
+            }
+        }
+    }
+    // Dropping `queue` on return lets the idle workers finish.
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn results_come_out_in_input_order() {
+        // Earlier items take longer, so they finish last.
+        let items: Vec<u64> = (0..8).collect();
+        let mut results = Vec::new();
+        map_ordered(
+            items,
+            4,
+            |i| {
+                thread::sleep(Duration::from_millis(8 - i));
+                i * 10
+            },
+            |result| {
+                results.push(result);
+                Ok::<(), ()>(())
+            },
+        )
+        .unwrap();
+        assert_eq!(results, [0, 10, 20, 30, 40, 50, 60, 70]);
+    }
+
+    #[test]
+    fn a_slow_item_holds_up_the_rest() {
+        // Item 0 is slow, so without a limit the others would all finish
+        // and wait for it.
+        let emitted = Arc::new(AtomicUsize::new(0));
+        let started = Arc::clone(&emitted);
+        let mut results = Vec::new();
+        map_ordered(
+            (0..40).collect(),
+            2,
+            move |i: usize| {
+                assert!(i < started.load(Ordering::SeqCst) + 2 * ITEMS_AHEAD_PER_JOB);
+                if i == 0 {
+                    thread::sleep(Duration::from_millis(50));
+                }
+                i
+            },
+            |i| {
+                results.push(i);
+                emitted.fetch_add(1, Ordering::SeqCst);
+                Ok::<(), ()>(())
+            },
+        )
+        .unwrap();
+        assert_eq!(results, (0..40).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn stops_at_the_first_emit_error() {
+        let mut seen = 0;
+        let result = map_ordered(
+            (0..100).collect(),
+            3,
+            |i: i32| i,
+            |i| {
+                seen += 1;
+                if i == 5 { Err(i) } else { Ok(()) }
+            },
+        );
+        assert_eq!(result, Err(MapError::Emit(5)));
+        assert_eq!(seen, 6);
+    }
+
+    #[test]
+    fn reports_a_panicking_worker() {
+        let mut results = Vec::new();
+        let result = map_ordered(
+            (0..6).collect(),
+            2,
+            |i: i32| {
+                assert_ne!(i, 3, "item 3 is cursed");
+                i
+            },
+            |i| {
+                results.push(i);
+                Ok::<(), ()>(())
+            },
+        );
+        assert_eq!(result, Err(MapError::WorkerPanicked));
+        assert_eq!(results, [0, 1, 2]);
+    }
+}
@@ -0,0 +1,181 @@
+// minigrep's `--in-place` editing: every match in a file is replaced and the
+// file rewritten, or with `--dry-run` the changes are shown as a diff.
+//
+// The new contents go to a temporary file next to the original, which is then
+// renamed over it. A rename within a directory is atomic, so the file is never
+// seen half-written, even if minigrep is interrupted.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+
+use super::matcher::Matcher;
+use crate::fsutil::TempFile;
+
+/// Replaces the matches in every line of `path` with `replacement` and returns
+/// how many lines changed. The file is only rewritten if any did.
+///
+/// With `dry_run`, the file is left alone and the changes are written to
+/// `out` as a unified diff instead. Line endings are kept as they are, and
+/// lines that aren't valid UTF-8 are never changed. Binary files, with a NUL
+/// byte near the start, are skipped.
+pub fn edit_file(
+    path: &Path,
+    matcher: &Matcher,
+    replacement: &str,
+    dry_run: bool,
+    out: &mut dyn Write,
+) -> io::Result<usize> {
+    let mut reader = BufReader::new(File::open(path)?);
+    if reader.fill_buf()?.contains(&0) {
+        return Ok(0);
+    }
+    let mut temp = if dry_run {
+        None
+    } else {
+        Some(TempFile::next_to(path)?)
+    };
+
+    let mut buf = Vec::new();
+    let mut number = 0;
+    let mut changed = 0;
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        number += 1;
+        let content_len = buf.len() - line_ending(&buf).len();
+        let (content, ending) = buf.split_at(content_len);
+
+        let replaced = match std::str::from_utf8(content) {
+            Ok(text) if matcher.is_match(text) => Some(matcher.replace(text, replacement).0),
+            _ => None,
+        };
+        let new_content = match &replaced {
+            Some(new) if new.as_bytes() != content => {
+                changed += 1;
+                if dry_run {
+                    if changed == 1 {
+                        writeln!(out, "--- {}", path.display())?;
+                        writeln!(out, "+++ {}", path.display())?;
+                    }
+                    let old = String::from_utf8_lossy(content);
+                    writeln!(out, "@@ -{number} +{number} @@\n-{old}\n+{new}")?;
+                }
+                new.as_bytes()
+            }
+            _ => content,
+        };
+        if let Some(temp) = &mut temp {
+            temp.writer.write_all(new_content)?;
+            temp.writer.write_all(ending)?;
+        }
+    }
+
+    if let Some(temp) = temp
+        && changed > 0
+    {
+        temp.replace(path)?;
+    }
+    Ok(changed)
+}
+
+// The `\n` or `\r\n` at the end of `line`, if any.
+fn line_ending(line: &[u8]) -> &[u8] {
+    if line.ends_with(b"\r\n") {
+        &line[line.len() - 2..]
+    } else if line.ends_with(b"\n") {
+        &line[line.len() - 1..]
+    } else {
+        &[]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ch12::matcher::Mode;
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+
+    // A scratch file with `contents`, unique to the test.
+    fn scratch(name: &str, contents: &[u8]) -> PathBuf {
+        let path = env::temp_dir().join(format!("minigrep-{}-{name}", process::id()));
+        fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn empty_matches_change_nothing() {
+        let path = scratch("empty.txt", b"abc\nxyz\n");
+        let matcher = Matcher::new("x*", Mode::Regex, false).unwrap();
+        assert_eq!(
+            edit_file(&path, &matcher, "-", false, &mut io::sink()).unwrap(),
+            1
+        );
+        assert_eq!(fs::read(&path).unwrap(), b"abc\n-yz\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn rewrites_matching_lines_and_keeps_the_rest() {
+        let path = scratch("rewrite.txt", b"one fish\r\ntwo fish\nred \xff fish\nblue");
+        let matcher = Matcher::new(r"(\w+) fish", Mode::Regex, false).unwrap();
+        let mut out = Vec::new();
+
+        let changed = edit_file(&path, &matcher, "fish $1", false, &mut out).unwrap();
+        assert_eq!(changed, 2);
+        assert!(out.is_empty());
+        assert_eq!(
+            fs::read(&path).unwrap(),
+            b"fish one\r\nfish two\nred \xff fish\nblue"
+        );
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn dry_run_prints_a_diff() {
+        let path = scratch("dry-run.txt", b"a cat\nno match\nthe cat sat\n");
+        let matcher = Matcher::new("cat", Mode::Substring, false).unwrap();
+        let mut out = Vec::new();
+
+        let changed = edit_file(&path, &matcher, "dog", true, &mut out).unwrap();
+        assert_eq!(changed, 2);
+        let name = path.display();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "--- {name}\n+++ {name}\n\
+                 @@ -1 +1 @@\n-a cat\n+a dog\n\
+                 @@ -3 +3 @@\n-the cat sat\n+the dog sat\n"
+            )
+        );
+        assert_eq!(fs::read(&path).unwrap(), b"a cat\nno match\nthe cat sat\n");
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn unchanged_files_are_not_rewritten() {
+        let path = scratch("unchanged.txt", b"nothing here\n");
+        let before = fs::metadata(&path).unwrap().modified().unwrap();
+        let matcher = Matcher::new("cat", Mode::Substring, false).unwrap();
+
+        assert_eq!(
+            edit_file(&path, &matcher, "dog", false, &mut io::sink()).unwrap(),
+            0
+        );
+        assert_eq!(fs::metadata(&path).unwrap().modified().unwrap(), before);
+        let leftovers = fs::read_dir(env::temp_dir())
+            .unwrap()
+            .filter_map(Result::ok)
+            .any(|entry| {
+                let name = entry.file_name().to_string_lossy().into_owned();
+                name.starts_with(&format!(".minigrep-{}-unchanged", process::id()))
+            });
+        assert!(!leftovers);
+        fs::remove_file(&path).unwrap();
+    }
+}
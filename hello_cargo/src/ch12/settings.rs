@@ -0,0 +1,386 @@
+// Default options for minigrep from outside the command line.
+//
+// Settings are layered, each overriding the one before:
+//
+//   1. the config file, `$XDG_CONFIG_HOME/minigrep/config.toml` or
+//      `~/.config/minigrep/config.toml` (`MINIGREP_CONFIG` names another one,
+//      or none when empty)
+//   2. the environment variables `IGNORE_CASE` and `MINIGREP_COLOR`
+//   3. the command line
+//
+// The config file uses a small part of TOML: `key = value` lines, where a key
+// is a long option name and a value is `true`, a number, a "string" or an
+// array of strings, and `#` starts a comment line:
+//
+//   ignore-case = true
+//   color = "always"
+//   exclude = ["target", "*.lock"]
+//
+// Every layer is turned into the options it stands for, so applying them in
+// order gives later layers precedence.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::cli::{ArgsError, Found, Opt};
+
+/// Where a setting came from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env(&'static str),
+    /// The option as written on the command line.
+    Flag(String),
+    /// A positional argument on the command line.
+    Argument,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File(path) => write!(f, "config file {}", path.display()),
+            Source::Env(var) => write!(f, "environment variable {var}"),
+            Source::Flag(name) => write!(f, "command line {name}"),
+            Source::Argument => write!(f, "command line argument"),
+        }
+    }
+}
+
+/// The layers under the command line.
+#[derive(Debug, Clone, Default)]
+pub struct Defaults {
+    /// The config file's path and contents, if there is one.
+    pub file: Option<(PathBuf, String)>,
+    /// The environment variables minigrep reads that are set, with their values.
+    pub env: Vec<(&'static str, String)>,
+}
+
+/// The environment variables minigrep reads, and the option each stands for.
+/// `IGNORE_CASE` only needs to be set; its value doesn't matter.
+pub const ENV_VARS: &[(&str, &str)] =
+    &[("IGNORE_CASE", "ignore-case"), ("MINIGREP_COLOR", "color")];
+
+impl Defaults {
+    /// Reads the config file and environment variables.
+    ///
+    /// A missing config file is fine; one that can't be read is an error.
+    pub fn load() -> Result<Defaults, ArgsError> {
+        let file = match config_path() {
+            Some(path) => match fs::read_to_string(&path) {
+                Ok(text) => Some((path, text)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+                Err(e) => {
+                    return Err(ArgsError::InvalidConfig(format!("{}: {e}", path.display())));
+                }
+            },
+            None => None,
+        };
+        let env = ENV_VARS
+            .iter()
+            .filter_map(|&(var, _)| {
+                env::var_os(var).map(|value| (var, value.to_string_lossy().into_owned()))
+            })
+            .collect();
+        Ok(Defaults { file, env })
+    }
+
+    /// The options the layers stand for, lowest precedence first.
+    pub fn options(&self, opts: &'static [Opt]) -> Result<Vec<(Found, Source)>, ArgsError> {
+        let mut options = Vec::new();
+        if let Some((path, text)) = &self.file {
+            for found in parse_file(opts, path, text)? {
+                options.push((found, Source::File(path.clone())));
+            }
+        }
+        for &(var, ref value) in &self.env {
+            let Some(&(_, long)) = ENV_VARS.iter().find(|&&(name, _)| name == var) else {
+                continue;
+            };
+            let opt = find(opts, long).expect("environment variables name known options");
+            let found = Found {
+                opt,
+                name: var.to_string(),
+                value: opt.value.map(|_| value.clone()),
+            };
+            options.push((found, Source::Env(var)));
+        }
+        Ok(options)
+    }
+}
+
+/// The config file to read, if any.
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("MINIGREP_CONFIG") {
+        return (!path.is_empty()).then(|| PathBuf::from(path));
+    }
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("minigrep").join("config.toml"))
+}
+
+// Options that only make sense on the command line, or that would be
+// dangerous as a default.
+const COMMAND_LINE_ONLY: &[&str] = &[
+    "help",
+    "version",
+    "print-config",
+    "replace",
+    "in-place",
+    "dry-run",
+];
+
+fn find(opts: &'static [Opt], long: &str) -> Option<&'static Opt> {
+    opts.iter()
+        .find(|opt| opt.long == long || opt.alias == Some(long))
+}
+
+/// The options set in the config file `path`, whose contents are `text`.
+pub fn parse_file(opts: &'static [Opt], path: &Path, text: &str) -> Result<Vec<Found>, ArgsError> {
+    let mut options = Vec::new();
+
+    for (i, line) in text.lines().enumerate() {
+        let error = |reason: String| {
+            ArgsError::InvalidConfig(format!("{}:{}: {reason}", path.display(), i + 1))
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error(String::from("expected `key = value`")))?;
+        let key = key.trim();
+        let opt = find(opts, key)
+            .filter(|opt| !COMMAND_LINE_ONLY.contains(&opt.long))
+            .ok_or_else(|| error(format!("unknown setting `{key}`")))?;
+        let name = format!("`{key}` in {}", path.display());
+
+        match (parse_value(value.trim()).map_err(error)?, opt.value) {
+            (Value::Bool(true), None) => options.push(Found {
+                opt,
+                name,
+                value: None,
+            }),
+            (Value::Bool(false), None) => {}
+            (Value::Scalar(value), Some(_)) => options.push(Found {
+                opt,
+                name,
+                value: Some(value),
+            }),
+            (Value::Array(values), Some(_)) => {
+                for value in values {
+                    options.push(Found {
+                        opt,
+                        name: name.clone(),
+                        value: Some(value),
+                    });
+                }
+            }
+            (_, None) => return Err(error(format!("`{key}` must be true or false"))),
+            (Value::Bool(_), Some(value)) => {
+                return Err(error(format!("`{key}` needs a {value}, not true or false")));
+            }
+        }
+    }
+
+    Ok(options)
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Bool(bool),
+    /// A string or number, as the text an option would get.
+    Scalar(String),
+    Array(Vec<String>),
+}
+
+fn parse_value(text: &str) -> Result<Value, String> {
+    match text {
+        "true" => return Ok(Value::Bool(true)),
+        "false" => return Ok(Value::Bool(false)),
+        _ => {}
+    }
+    if text.starts_with('"') {
+        let (value, rest) = parse_string(text)?;
+        return if rest.trim().is_empty() {
+            Ok(Value::Scalar(value))
+        } else {
+            Err(format!("unexpected `{}` after string", rest.trim()))
+        };
+    }
+    if let Some(inner) = text.strip_prefix('[') {
+        let mut values = Vec::new();
+        let mut rest = inner.trim_start();
+        loop {
+            if let Some(after) = rest.strip_prefix(']') {
+                if !after.trim().is_empty() {
+                    return Err(format!("unexpected `{}` after array", after.trim()));
+                }
+                return Ok(Value::Array(values));
+            }
+            let (value, after) = parse_string(rest)?;
+            values.push(value);
+            rest = after.trim_start();
+            if let Some(after) = rest.strip_prefix(',') {
+                rest = after.trim_start();
+            } else if !rest.starts_with(']') {
+                return Err(String::from("expected `,` or `]` in array"));
+            }
+        }
+    }
+    if !text.is_empty() && text.chars().all(|c| c.is_ascii_digit()) {
+        return Ok(Value::Scalar(text.to_string()));
+    }
+    Err(format!(
+        "invalid value `{text}`; expected true, false, a number, a \"string\" or an array"
+    ))
+}
+
+// A quoted string at the start of `text`, with `\"` and `\\` escapes, and the
+// text after it.
+fn parse_string(text: &str) -> Result<(String, &str), String> {
+    let mut chars = text
+        .strip_prefix('"')
+        .ok_or_else(|| format!("expected a \"string\" at `{text}`"))?
+        .char_indices();
+    let mut value = String::new();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &text[i + 2..])),
+            '\\' => match chars.next() {
+                Some((_, c @ ('"' | '\\'))) => value.push(c),
+                Some((_, 't')) => value.push('\t'),
+                _ => return Err(String::from("unsupported escape in string")),
+            },
+            c => value.push(c),
+        }
+    }
+    Err(String::from("unterminated string"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTS: &[Opt] = &[
+        Opt::new(Some('i'), "ignore-case", ""),
+        Opt::new(None, "no-ignore", ""),
+        Opt::new(None, "color", "").value("when"),
+        Opt::new(None, "exclude", "").value("glob"),
+        Opt::new(Some('C'), "context", "").value("n"),
+        Opt::new(Some('h'), "help", ""),
+    ];
+
+    fn parse(text: &str) -> Result<Vec<(&'static str, Option<String>)>, ArgsError> {
+        let found = parse_file(OPTS, Path::new("config.toml"), text)?;
+        Ok(found
+            .into_iter()
+            .map(|found| (found.opt.long, found.value))
+            .collect())
+    }
+
+    #[test]
+    fn settings() {
+        let text = "\
+# my defaults
+ignore-case = true
+no-ignore = false
+color = \"always\"
+
+exclude = [\"target\", \"*.lock\" ]
+context = 2
+";
+        assert_eq!(
+            parse(text).unwrap(),
+            [
+                ("ignore-case", None),
+                ("color", Some(String::from("always"))),
+                ("exclude", Some(String::from("target"))),
+                ("exclude", Some(String::from("*.lock"))),
+                ("context", Some(String::from("2"))),
+            ]
+        );
+        assert_eq!(parse("exclude = []").unwrap(), []);
+        assert_eq!(
+            parse(r#"exclude = "a \"b\" \\ c""#).unwrap(),
+            [("exclude", Some(String::from(r#"a "b" \ c"#)))]
+        );
+    }
+
+    #[test]
+    fn errors_name_the_line() {
+        let error = |text| parse(text).unwrap_err().to_string();
+        assert_eq!(
+            error("color = \"never\"\nbogus = 1"),
+            "config.toml:2: unknown setting `bogus`"
+        );
+        assert_eq!(
+            error("help = true"),
+            "config.toml:1: unknown setting `help`"
+        );
+        assert_eq!(
+            error("ignore-case"),
+            "config.toml:1: expected `key = value`"
+        );
+        assert_eq!(
+            error("ignore-case = \"yes\""),
+            "config.toml:1: `ignore-case` must be true or false"
+        );
+        assert_eq!(
+            error("color = true"),
+            "config.toml:1: `color` needs a when, not true or false"
+        );
+        assert_eq!(
+            error("color = \"never"),
+            "config.toml:1: unterminated string"
+        );
+        assert_eq!(
+            error("exclude = [\"a\" \"b\"]"),
+            "config.toml:1: expected `,` or `]` in array"
+        );
+        assert!(error("context = two").starts_with("config.toml:1: invalid value `two`"));
+    }
+
+    #[test]
+    fn layers_come_in_order() {
+        let defaults = Defaults {
+            file: Some((
+                PathBuf::from("config.toml"),
+                String::from("color = \"never\""),
+            )),
+            env: vec![
+                ("MINIGREP_COLOR", String::from("always")),
+                ("IGNORE_CASE", String::new()),
+            ],
+        };
+        let options: Vec<_> = defaults
+            .options(OPTS)
+            .unwrap()
+            .into_iter()
+            .map(|(found, source)| (found.opt.long, found.value, source))
+            .collect();
+        assert_eq!(
+            options,
+            [
+                (
+                    "color",
+                    Some(String::from("never")),
+                    Source::File(PathBuf::from("config.toml"))
+                ),
+                (
+                    "color",
+                    Some(String::from("always")),
+                    Source::Env("MINIGREP_COLOR")
+                ),
+                ("ignore-case", None, Source::Env("IGNORE_CASE")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,367 @@
+// Expands minigrep's path arguments into the list of files to search.
+//
+// Directories are walked recursively in sorted order, so results come out in
+// the same order on every run. While walking, files are filtered by the
+// --include/--exclude globs and by `.gitignore` files found along the way.
+// Symbolic links to directories are only followed when named on the command
+// line, so a link back up the tree can't send the walk round in circles.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use super::glob::Glob;
+
+/// Name of the ignore files honored while walking directories.
+pub const IGNORE_FILE: &str = ".gitignore";
+
+/// Which files a directory walk keeps.
+#[derive(Debug, Clone, Default)]
+pub struct WalkOptions {
+    /// If not empty, only files matching one of these are searched.
+    pub include: Vec<Glob>,
+    /// Files and directories matching any of these are skipped.
+    pub exclude: Vec<Glob>,
+    /// Whether `.gitignore` files are honored.
+    pub use_ignore_files: bool,
+}
+
+impl WalkOptions {
+    // Globs without a `/` match the file name, others the path below the
+    // directory given on the command line.
+    fn glob_matches(glob: &Glob, relative: &str, name: &str) -> bool {
+        if glob.has_slash() {
+            glob.is_match(relative)
+        } else {
+            glob.is_match(name)
+        }
+    }
+
+    fn excluded(&self, relative: &str, name: &str) -> bool {
+        self.exclude
+            .iter()
+            .any(|glob| Self::glob_matches(glob, relative, name))
+    }
+
+    fn included(&self, relative: &str, name: &str) -> bool {
+        self.include.is_empty()
+            || self
+                .include
+                .iter()
+                .any(|glob| Self::glob_matches(glob, relative, name))
+    }
+}
+
+/// The files to search for `paths`, plus the paths that couldn't be read.
+///
+/// Files named on the command line are always kept; the options only filter
+/// what is found inside directories.
+pub fn files(
+    paths: &[PathBuf],
+    options: &WalkOptions,
+) -> (Vec<PathBuf>, Vec<(PathBuf, io::Error)>) {
+    let mut found = Vec::new();
+    let mut errors = Vec::new();
+
+    for path in paths {
+        // `-` stands for standard input.
+        if path == Path::new("-") {
+            found.push(path.clone());
+            continue;
+        }
+        match fs::metadata(path) {
+            Ok(metadata) if metadata.is_dir() => {
+                // Ignore rules are matched against absolute paths, so rules from
+                // directories above `path` apply the same way as those below it.
+                let absolute = fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+                let mut ignores = if options.use_ignore_files {
+                    ancestor_ignores(&absolute, &mut errors)
+                } else {
+                    Vec::new()
+                };
+                let root = Root {
+                    shown: path,
+                    absolute: &absolute,
+                };
+                walk(&root, path, options, &mut ignores, &mut found, &mut errors);
+            }
+            Ok(_) => found.push(path.clone()),
+            Err(e) => errors.push((path.clone(), e)),
+        }
+    }
+
+    (found, errors)
+}
+
+// A directory named on the command line, as given and as an absolute path.
+struct Root<'a> {
+    shown: &'a Path,
+    absolute: &'a Path,
+}
+
+impl Root<'_> {
+    fn absolute(&self, path: &Path) -> PathBuf {
+        match path.strip_prefix(self.shown) {
+            Ok(relative) => self.absolute.join(relative),
+            Err(_) => path.to_path_buf(),
+        }
+    }
+}
+
+// The ignore files between the enclosing git repository's root and `dir`
+// (exclusive), outermost first. Outside a repository there are none.
+fn ancestor_ignores(dir: &Path, errors: &mut Vec<(PathBuf, io::Error)>) -> Vec<IgnoreRules> {
+    let Some(repo_root) = dir.ancestors().find(|d| d.join(".git").exists()) else {
+        return Vec::new();
+    };
+    let mut ancestors: Vec<&Path> = dir
+        .ancestors()
+        .skip(1)
+        .take_while(|d| d.starts_with(repo_root))
+        .collect();
+    ancestors.reverse();
+
+    ancestors
+        .into_iter()
+        .filter_map(|d| match IgnoreRules::load(d) {
+            Ok(rules) => rules,
+            Err(e) => {
+                errors.push((d.join(IGNORE_FILE), e));
+                None
+            }
+        })
+        .collect()
+}
+
+fn walk(
+    root: &Root,
+    dir: &Path,
+    options: &WalkOptions,
+    ignores: &mut Vec<IgnoreRules>,
+    found: &mut Vec<PathBuf>,
+    errors: &mut Vec<(PathBuf, io::Error)>,
+) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            errors.push((dir.to_path_buf(), e));
+            return;
+        }
+    };
+    let mut entries: Vec<_> = entries
+        .filter_map(Result::ok)
+        .filter_map(|entry| Some((entry.path(), entry.file_type().ok()?)))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let pushed = if options.use_ignore_files {
+        match IgnoreRules::load(&root.absolute(dir)) {
+            Ok(Some(rules)) => {
+                ignores.push(rules);
+                true
+            }
+            Ok(None) => false,
+            Err(e) => {
+                errors.push((dir.join(IGNORE_FILE), e));
+                false
+            }
+        }
+    } else {
+        false
+    };
+
+    for (path, file_type) in entries {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        if name == ".git" {
+            continue;
+        }
+        let relative = slash_path(path.strip_prefix(root.shown).unwrap_or(&path));
+        // Links to files are searched like the files themselves.
+        if file_type.is_symlink() && path.is_dir() {
+            continue;
+        }
+        let is_dir = file_type.is_dir();
+
+        if options.excluded(&relative, &name) || is_ignored(ignores, &root.absolute(&path), is_dir)
+        {
+            continue;
+        }
+        if is_dir {
+            walk(root, &path, options, ignores, found, errors);
+        } else if options.included(&relative, &name) {
+            found.push(path);
+        }
+    }
+
+    if pushed {
+        ignores.pop();
+    }
+}
+
+// Last matching rule wins, with deeper ignore files applied after shallower ones.
+fn is_ignored(ignores: &[IgnoreRules], path: &Path, is_dir: bool) -> bool {
+    let mut ignored = false;
+    for rules in ignores {
+        if let Some(decision) = rules.decide(path, is_dir) {
+            ignored = decision;
+        }
+    }
+    ignored
+}
+
+// Paths as glob patterns see them: relative, `/`-separated.
+fn slash_path(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[derive(Debug)]
+struct IgnoreRule {
+    glob: Glob,
+    negated: bool,
+    dir_only: bool,
+    // Anchored rules match the path below the ignore file's directory,
+    // the others match the file name at any depth.
+    anchored: bool,
+}
+
+/// The rules of one `.gitignore`-style file.
+#[derive(Debug)]
+pub struct IgnoreRules {
+    base: PathBuf,
+    rules: Vec<IgnoreRule>,
+}
+
+impl IgnoreRules {
+    /// Reads `dir/.gitignore`, if there is one.
+    pub fn load(dir: &Path) -> io::Result<Option<IgnoreRules>> {
+        match fs::read_to_string(dir.join(IGNORE_FILE)) {
+            Ok(text) => Ok(Some(IgnoreRules::parse(dir, &text))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Parses ignore-file `text` whose rules apply below `base`.
+    ///
+    /// Supports comments, `!` negation, trailing `/` for directories only and
+    /// leading or inner `/` for anchored patterns. Lines with invalid globs
+    /// are skipped, as git does.
+    pub fn parse(base: &Path, text: &str) -> IgnoreRules {
+        let rules = text
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim_end();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (negated, line) = match line.strip_prefix('!') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let (dir_only, line) = match line.strip_suffix('/') {
+                    Some(rest) => (true, rest),
+                    None => (false, line),
+                };
+                let anchored = line.contains('/');
+                let line = line.strip_prefix('/').unwrap_or(line);
+                let glob = Glob::new(line).ok()?;
+                Some(IgnoreRule {
+                    glob,
+                    negated,
+                    dir_only,
+                    anchored,
+                })
+            })
+            .collect();
+
+        IgnoreRules {
+            base: base.to_path_buf(),
+            rules,
+        }
+    }
+
+    /// `Some(true)` if the last rule matching `path` ignores it, `Some(false)`
+    /// if it re-includes it and `None` if no rule matches.
+    pub fn decide(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let relative = slash_path(path.strip_prefix(&self.base).ok()?);
+        let name = path.file_name()?.to_string_lossy();
+
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| {
+                (is_dir || !rule.dir_only)
+                    && if rule.anchored {
+                        rule.glob.is_match(&relative)
+                    } else {
+                        rule.glob.is_match(&name)
+                    }
+            })
+            .map(|rule| !rule.negated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(text: &str) -> IgnoreRules {
+        IgnoreRules::parse(Path::new("repo"), text)
+    }
+
+    #[test]
+    fn ignore_rules() {
+        let rules = rules("# build output\ntarget/\n*.log\n!keep.log\n/Cargo.lock\ndocs/*.html\n");
+        let decide = |path: &str, is_dir| rules.decide(&Path::new("repo").join(path), is_dir);
+
+        assert_eq!(decide("target", true), Some(true));
+        assert_eq!(decide("target", false), None);
+        assert_eq!(decide("sub/debug.log", false), Some(true));
+        assert_eq!(decide("keep.log", false), Some(false));
+        assert_eq!(decide("Cargo.lock", false), Some(true));
+        assert_eq!(decide("sub/Cargo.lock", false), None);
+        assert_eq!(decide("docs/index.html", false), Some(true));
+        assert_eq!(decide("src/main.rs", false), None);
+    }
+
+    #[test]
+    fn walks_this_crate_in_sorted_order() {
+        let src = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("ch8");
+        let options = WalkOptions {
+            include: vec![Glob::new("*.rs").unwrap()],
+            exclude: vec![Glob::new("mod.rs").unwrap()],
+            use_ignore_files: true,
+        };
+        let (files, errors) = files(std::slice::from_ref(&src), &options);
+        assert!(errors.is_empty());
+        let names: Vec<_> = files
+            .iter()
+            .map(|file| slash_path(file.strip_prefix(&src).unwrap()))
+            .collect();
+        assert_eq!(names, ["ch8_1.rs", "ch8_2.rs", "ch8_3.rs"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn does_not_follow_links_to_directories() {
+        let dir = std::env::temp_dir().join(format!("minigrep-walk-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(dir.join("d")).unwrap();
+        fs::write(dir.join("d").join("a.txt"), "a\n").unwrap();
+        std::os::unix::fs::symlink("..", dir.join("d").join("up")).unwrap();
+        std::os::unix::fs::symlink("a.txt", dir.join("d").join("b.txt")).unwrap();
+
+        let (files, errors) = files(std::slice::from_ref(&dir), &WalkOptions::default());
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(errors.is_empty(), "{errors:?}");
+        assert_eq!(
+            files,
+            [dir.join("d").join("a.txt"), dir.join("d").join("b.txt")]
+        );
+    }
+}
@@ -13,24 +13,26 @@
 // - Syntax is concise and often omits type annotations (inferred).
 // - Can take ownership, borrow mutably, or borrow immutably from the environment.
 
+use std::io::{self, Write};
+
 #[derive(Debug, PartialEq, Copy, Clone)]
-enum ShirtColor {
+pub enum ShirtColor {
     Red,
     Blue,
 }
 
-struct Inventory {
-    shirts: Vec<ShirtColor>,
+pub struct Inventory {
+    pub shirts: Vec<ShirtColor>,
 }
 
 impl Inventory {
     // giveaway uses a closure as argument to Option::unwrap_or_else
-    fn giveaway(&self, user_preference: Option<ShirtColor>) -> ShirtColor {
+    pub fn giveaway(&self, user_preference: Option<ShirtColor>) -> ShirtColor {
         // Closure captures &self and can call methods on it.
         user_preference.unwrap_or_else(|| self.most_stocked())
     }
 
-    fn most_stocked(&self) -> ShirtColor {
+    pub fn most_stocked(&self) -> ShirtColor {
         let mut num_red = 0;
         let mut num_blue = 0;
         for color in &self.shirts {
@@ -43,8 +45,8 @@ impl Inventory {
     }
 }
 
-fn ch13() {
-    println!("=== Closure Examples ===");
+pub fn ch13(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "=== Closure Examples ===")?;
 
     // Example: Closure capturing self
     let store = Inventory {
@@ -52,36 +54,38 @@ fn ch13() {
     };
     let user_pref1 = Some(ShirtColor::Red);
     let giveaway1 = store.giveaway(user_pref1);
-    println!("The user with preference {:?} gets {:?}", user_pref1, giveaway1);
+    writeln!(out, "The user with preference {:?} gets {:?}", user_pref1, giveaway1)?;
 
     let user_pref2 = None;
     let giveaway2 = store.giveaway(user_pref2);
-    println!("The user with preference {:?} gets {:?}", user_pref2, giveaway2);
+    writeln!(out, "The user with preference {:?} gets {:?}", user_pref2, giveaway2)?;
 
     // Closure type syntax and inference:
     // - Closures can have explicit or inferred parameter/return types
     let add_one_v1 = |x: u32| -> u32 { x + 1 };
     let add_one_v2 = |x| x + 1;
-    println!("add_one_v1(5) = {}, add_one_v2(10) = {}", add_one_v1(5), add_one_v2(10));
+    writeln!(out, "add_one_v1(5) = {}, add_one_v2(10) = {}", add_one_v1(5), add_one_v2(10))?;
 
     // Closure captures immutable reference
     let list = vec![1, 2, 3];
-    let only_borrows = || println!("From closure: {:?}", list);
-    only_borrows();
+    let only_borrows = || format!("From closure: {:?}", list);
+    writeln!(out, "{}", only_borrows())?;
 
     // Closure captures mutable reference
     let mut list2 = vec![1, 2, 3];
     let mut borrows_mutably = || list2.push(7);
     borrows_mutably();
-    println!("After mut closure: {:?}", list2);
+    writeln!(out, "After mut closure: {:?}", list2)?;
 
     // Move closure for threads
     use std::thread;
     let list3 = vec![1, 2, 3];
-    thread::spawn(move || println!("From thread: {:?}", list3)).join().unwrap();
+    let from_thread = thread::spawn(move || format!("From thread: {:?}", list3)).join().unwrap();
+    writeln!(out, "{from_thread}")?;
 
     // Closure type is inferred from first call:
     let example_closure = |x| x;
+    #[allow(unused_variables)]
     let s = example_closure(String::from("hello"));
     // let n = example_closure(5); // Would not compile, type is String
 
@@ -95,6 +99,9 @@ fn ch13() {
     //   - FnMut: can be called multiple times, may mutate environment.
     //   - Fn: can be called multiple times, does not mutate environment.
     // - The compiler infers how closures capture environment and which trait is needed depending on usage (e.g. in threads, iterator methods).
+
+    iterator_examples(out)?;
+    filter_shoes_example(out)
 }
 
 // =================================================
@@ -109,13 +116,14 @@ fn ch13() {
 // - Iterators are lazy: nothing happens until a consuming adapter (like sum, collect, for, etc.) is called.
 // - Adapters (like map, filter) create new iterators, which can be chained.
 
-fn iterator_examples() {
-    println!("\n=== Iterator Examples ===");
+#[allow(clippy::useless_vec)] // the book iterates over vectors
+fn iterator_examples(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n=== Iterator Examples ===")?;
 
     let v1 = vec![1, 2, 3];
     let v1_iter = v1.iter();
     for val in v1_iter {
-        println!("Got: {val}");
+        writeln!(out, "Got: {val}")?;
     }
 
     // The Iterator trait and next:
@@ -145,6 +153,7 @@ fn iterator_examples() {
     // - Chaining: Adapters can be chained for complex logic.
     // - Zero-cost abstraction: Compiler optimizes away the abstraction.
     // - Iterator methods often take closures for customization.
+    Ok(())
 }
 
 // =================================================
@@ -154,18 +163,18 @@ fn iterator_examples() {
 // Closures are commonly used with iterators to customize how items are processed.
 
 #[derive(PartialEq, Debug)]
-struct Shoe {
-    size: u32,
-    style: String,
+pub struct Shoe {
+    pub size: u32,
+    pub style: String,
 }
 
-fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe> {
+pub fn shoes_in_size(shoes: Vec<Shoe>, shoe_size: u32) -> Vec<Shoe> {
     // `filter` takes a closure that captures shoe_size from its environment.
     shoes.into_iter().filter(|s| s.size == shoe_size).collect()
 }
 
-fn filter_shoes_example() {
-    println!("\n=== Filtering Shoes Example ===");
+fn filter_shoes_example(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "\n=== Filtering Shoes Example ===")?;
     let shoes = vec![
         Shoe { size: 10, style: String::from("sneaker") },
         Shoe { size: 13, style: String::from("sandal") },
@@ -179,7 +188,8 @@ fn filter_shoes_example() {
             Shoe { size: 10, style: String::from("boot") },
         ]
     );
-    println!("Shoes in my size: {:?}", in_my_size);
+    writeln!(out, "Shoes in my size: {:?}", in_my_size)?;
+    Ok(())
 }
 
 // =================================================
@@ -0,0 +1,29 @@
+#![allow(clippy::module_inception)]
+
+pub mod ch13;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch13;
+
+impl Chapter for Ch13 {
+    fn id(&self) -> &'static str {
+        "ch13"
+    }
+
+    fn title(&self) -> &'static str {
+        "Functional Language Features: Iterators and Closures"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["closures", "Fn", "FnMut", "FnOnce", "iterators", "map", "filter", "collect"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch13::ch13(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch13];
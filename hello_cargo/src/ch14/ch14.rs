@@ -121,6 +121,9 @@
 // - Install binaries with cargo install.
 // - Extend Cargo with custom subcommands.
 
-fn ch14() {
-    println!("See source for full notes and examples on publishing, workspaces, and customizing Rust projects with Cargo.");
+use std::io::{self, Write};
+
+pub fn ch14(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "See source for full notes and examples on publishing, workspaces, and customizing Rust projects with Cargo.")?;
+    Ok(())
 }
\ No newline at end of file
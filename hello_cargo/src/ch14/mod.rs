@@ -0,0 +1,29 @@
+#![allow(clippy::module_inception)]
+
+pub mod ch14;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch14;
+
+impl Chapter for Ch14 {
+    fn id(&self) -> &'static str {
+        "ch14"
+    }
+
+    fn title(&self) -> &'static str {
+        "More About Cargo and Crates.io"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["cargo", "release profiles", "documentation comments", "pub use", "workspaces", "cargo install"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch14::ch14(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch14];
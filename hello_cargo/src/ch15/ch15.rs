@@ -7,9 +7,12 @@
 //   * Want to transfer ownership of large data without copying it
 //   * Want to own a value of any type implementing a trait (trait objects, see Chapter 18)
 
-fn box_example() {
+use std::io::{self, Write};
+
+fn box_example(out: &mut dyn Write) -> io::Result<()> {
     let b = Box::new(5);
-    println!("b = {b}");
+    writeln!(out, "b = {b}")?;
+    Ok(())
 }
 
 // --- Enabling Recursive Types with Boxes ---
@@ -17,7 +20,7 @@ fn box_example() {
 // - Recursive types (like a linked list) have unknown size unless you use indirection (Box, Rc, etc).
 // - Example: Cons list (linked list)
 
-enum List {
+pub enum List {
     Cons(i32, Box<List>),
     Nil,
 }
@@ -25,6 +28,7 @@ enum List {
 use List::{Cons, Nil};
 
 fn recursive_type_example() {
+    #[allow(unused_variables)]
     let list = Cons(1, Box::new(Cons(2, Box::new(Cons(3, Box::new(Nil))))));
     // This works because Box<List> has known size (pointer), breaking the infinite chain.
 }
@@ -35,10 +39,10 @@ fn recursive_type_example() {
 
 use std::ops::Deref;
 
-struct MyBox<T>(T);
+pub struct MyBox<T>(T);
 
 impl<T> MyBox<T> {
-    fn new(x: T) -> MyBox<T> { MyBox(x) }
+    pub fn new(x: T) -> MyBox<T> { MyBox(x) }
 }
 
 impl<T> Deref for MyBox<T> {
@@ -56,21 +60,36 @@ fn deref_example() {
 // - Drop lets you specify what happens when a value goes out of scope (destructor).
 // - Rust automatically calls drop; you can't call it manually, but you can use std::mem::drop to drop early.
 
-struct CustomSmartPointer {
-    data: String,
+// drop() can't take a writer or return an error, so this version records its
+// message in a shared log (a RefCell, see below) that the demo prints afterwards.
+pub struct CustomSmartPointer<'a> {
+    pub data: String,
+    pub drops: &'a RefCell<Vec<String>>,
 }
-impl Drop for CustomSmartPointer {
+impl Drop for CustomSmartPointer<'_> {
     fn drop(&mut self) {
-        println!("Dropping CustomSmartPointer with data `{}`!", self.data);
+        self.drops
+            .borrow_mut()
+            .push(format!("Dropping CustomSmartPointer with data `{}`!", self.data));
     }
 }
 
-fn drop_example() {
-    let c = CustomSmartPointer { data: String::from("my stuff") };
-    let d = CustomSmartPointer { data: String::from("other stuff") };
-    println!("CustomSmartPointers created.");
-    // d and then c are dropped at end of scope (in reverse order).
-    // To drop early: std::mem::drop(c);
+fn drop_example(out: &mut dyn Write) -> io::Result<()> {
+    let drops = RefCell::new(Vec::new());
+    {
+        // Named, not `_`, so they live until the end of the scope.
+        #[allow(unused_variables)]
+        let c = CustomSmartPointer { data: String::from("my stuff"), drops: &drops };
+        #[allow(unused_variables)]
+        let d = CustomSmartPointer { data: String::from("other stuff"), drops: &drops };
+        writeln!(out, "CustomSmartPointers created.")?;
+        // d and then c are dropped at end of scope (in reverse order).
+        // To drop early: std::mem::drop(c);
+    }
+    for message in drops.borrow().iter() {
+        writeln!(out, "{message}")?;
+    }
+    Ok(())
 }
 
 // --- Rc<T>: Reference Counting for Shared Ownership ---
@@ -80,23 +99,26 @@ fn drop_example() {
 
 use std::rc::Rc;
 
-enum RcList {
+pub enum RcList {
     RcCons(i32, Rc<RcList>),
     RcNil,
 }
 
 use RcList::{RcCons, RcNil};
 
-fn rc_example() {
+fn rc_example(out: &mut dyn Write) -> io::Result<()> {
     let a = Rc::new(RcCons(5, Rc::new(RcCons(10, Rc::new(RcNil)))));
-    println!("count after creating a = {}", Rc::strong_count(&a));
+    writeln!(out, "count after creating a = {}", Rc::strong_count(&a))?;
+    #[allow(unused_variables)]
     let b = RcCons(3, Rc::clone(&a));
-    println!("count after creating b = {}", Rc::strong_count(&a));
+    writeln!(out, "count after creating b = {}", Rc::strong_count(&a))?;
     {
+        #[allow(unused_variables)]
         let c = RcCons(4, Rc::clone(&a));
-        println!("count after creating c = {}", Rc::strong_count(&a));
+        writeln!(out, "count after creating c = {}", Rc::strong_count(&a))?;
     }
-    println!("count after c goes out of scope = {}", Rc::strong_count(&a));
+    writeln!(out, "count after c goes out of scope = {}", Rc::strong_count(&a))?;
+    Ok(())
 }
 
 // --- RefCell<T> and the Interior Mutability Pattern ---
@@ -136,6 +158,19 @@ where
     }
 }
 
+pub fn ch15(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "-- Box<T> --")?;
+    box_example(out)?;
+    recursive_type_example();
+    deref_example();
+    writeln!(out, "*MyBox::new(5) == 5")?;
+    writeln!(out, "-- Drop --")?;
+    drop_example(out)?;
+    writeln!(out, "-- Rc<T> --")?;
+    rc_example(out)?;
+    writeln!(out, "See source for notes on RefCell<T> and the LimitTracker mock-object test.")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,4 +205,3 @@ mod tests {
 // * Rc<T>: Multiple ownership, heap allocation, compile-time immutable borrowing, single-threaded.
 // * RefCell<T>: Single ownership, interior mutability, borrowing checked at runtime, single-threaded.
 // * Use Box<T> for recursive types and heap allocation; Rc<T> for shared ownership; RefCell<T> for interior mutability.
-//
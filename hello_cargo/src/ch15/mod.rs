@@ -0,0 +1,29 @@
+#![allow(clippy::module_inception)]
+
+pub mod ch15;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch15;
+
+impl Chapter for Ch15 {
+    fn id(&self) -> &'static str {
+        "ch15"
+    }
+
+    fn title(&self) -> &'static str {
+        "Smart Pointers"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["Box", "Deref", "Drop", "Rc", "RefCell", "interior mutability", "reference cycles"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch15::ch15(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch15];
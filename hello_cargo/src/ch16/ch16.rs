@@ -8,21 +8,33 @@
 // - thread::spawn launches a new thread, takes a closure.
 // - Threads may interleave unpredictably. Main thread ending will terminate all spawned threads.
 
+use std::io::{self, Write};
 use std::thread;
 use std::time::Duration;
 
-fn basic_thread_example() {
-    thread::spawn(|| {
+// Spawned threads can't borrow `out`, so in these demos they send their lines
+// back over a channel (section 4) and the main thread does the writing.
+
+fn basic_thread_example(out: &mut dyn Write) -> io::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
         for i in 1..10 {
-            println!("Spawned thread: hi number {i}");
+            // Stop quietly once the main thread is gone and the receiver is dropped.
+            if tx.send(format!("Spawned thread: hi number {i}")).is_err() {
+                break;
+            }
             thread::sleep(Duration::from_millis(1));
         }
     });
     for i in 1..5 {
-        println!("Main thread: hi number {i}");
+        writeln!(out, "Main thread: hi number {i}")?;
+        for line in rx.try_iter() {
+            writeln!(out, "{line}")?;
+        }
         thread::sleep(Duration::from_millis(1));
     }
     // Spawned thread may not finish before main ends!
+    Ok(())
 }
 
 // =============================================================
@@ -32,18 +44,26 @@ fn basic_thread_example() {
 // - thread::spawn returns a JoinHandle<T>.
 // - join() will block until the thread finishes.
 
-fn join_handle_example() {
-    let handle = thread::spawn(|| {
+fn join_handle_example(out: &mut dyn Write) -> io::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let handle = thread::spawn(move || {
         for i in 1..10 {
-            println!("Spawned thread: hi number {i}");
+            tx.send(format!("Spawned thread: hi number {i}")).unwrap();
             thread::sleep(Duration::from_millis(1));
         }
     });
     for i in 1..5 {
-        println!("Main thread: hi number {i}");
+        writeln!(out, "Main thread: hi number {i}")?;
+        for line in rx.try_iter() {
+            writeln!(out, "{line}")?;
+        }
         thread::sleep(Duration::from_millis(1));
     }
     handle.join().unwrap(); // Ensures spawned thread completes
+    for line in rx.try_iter() {
+        writeln!(out, "{line}")?;
+    }
+    Ok(())
 }
 
 // =============================================================
@@ -52,12 +72,10 @@ fn join_handle_example() {
 //
 // - move keyword moves ownership of captured variables into the closure/thread.
 
-fn move_closure_example() {
+fn move_closure_example(out: &mut dyn Write) -> io::Result<()> {
     let v = vec![1, 2, 3];
-    let handle = thread::spawn(move || {
-        println!("Here's a vector: {:?}", v);
-    });
-    handle.join().unwrap();
+    let handle = thread::spawn(move || format!("Here's a vector: {:?}", v));
+    writeln!(out, "{}", handle.join().unwrap())
 }
 
 // =============================================================
@@ -69,7 +87,7 @@ fn move_closure_example() {
 
 use std::sync::mpsc;
 
-fn channel_example() {
+fn channel_example(out: &mut dyn Write) -> io::Result<()> {
     let (tx, rx) = mpsc::channel();
     thread::spawn(move || {
         let vals = vec!["hi", "from", "the", "thread"];
@@ -79,8 +97,9 @@ fn channel_example() {
         }
     });
     for received in rx {
-        println!("Got: {received}");
+        writeln!(out, "Got: {received}")?;
     }
+    Ok(())
 }
 
 // =============================================================
@@ -89,7 +108,24 @@ fn channel_example() {
 //
 // - Clone the sender to have multiple producers.
 
-fn multi_producer_channel_example() {
+/// Runs each producer on its own thread with a clone of one sender and
+/// returns the receiver, which yields values as they arrive and ends once
+/// every producer has finished.
+pub fn fan_in<T, F>(producers: impl IntoIterator<Item = F>) -> mpsc::Receiver<T>
+where
+    T: Send + 'static,
+    F: FnOnce(mpsc::Sender<T>) + Send + 'static,
+{
+    let (tx, rx) = mpsc::channel();
+    for producer in producers {
+        let tx = tx.clone();
+        thread::spawn(move || producer(tx));
+    }
+    // The original sender is dropped here, so only the producers keep the channel open.
+    rx
+}
+
+fn multi_producer_channel_example(out: &mut dyn Write) -> io::Result<()> {
     let (tx, rx) = mpsc::channel();
     let tx1 = tx.clone();
     thread::spawn(move || {
@@ -107,8 +143,21 @@ fn multi_producer_channel_example() {
         }
     });
     for received in rx {
-        println!("Got: {received}");
+        writeln!(out, "Got: {received}")?;
     }
+
+    // The same pattern with fan_in: one producer per batch of words.
+    let batches = [["five", "six"], ["seven", "eight"]];
+    let rx = fan_in(batches.map(|vals| {
+        move |tx: mpsc::Sender<String>| {
+            for val in vals {
+                tx.send(String::from(val)).unwrap();
+            }
+        }
+    }));
+    let mut received: Vec<String> = rx.iter().collect();
+    received.sort();
+    writeln!(out, "fan_in got (sorted): {}", received.join(", "))
 }
 
 // =============================================================
@@ -121,10 +170,12 @@ fn multi_producer_channel_example() {
 
 use std::sync::{Arc, Mutex};
 
-fn mutex_arc_example() {
+/// Spawns `threads` threads that each add one to a counter shared as
+/// `Arc<Mutex<usize>>`, and returns the final count.
+pub fn parallel_count(threads: usize) -> usize {
     let counter = Arc::new(Mutex::new(0));
     let mut handles = vec![];
-    for _ in 0..10 {
+    for _ in 0..threads {
         let counter = Arc::clone(&counter);
         let handle = thread::spawn(move || {
             let mut num = counter.lock().unwrap();
@@ -135,7 +186,11 @@ fn mutex_arc_example() {
     for handle in handles {
         handle.join().unwrap();
     }
-    println!("Result: {}", *counter.lock().unwrap()); // Should print 10
+    *counter.lock().unwrap()
+}
+
+fn mutex_arc_example(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "Result: {}", parallel_count(10)) // Should print 10
 }
 
 // =============================================================
@@ -157,18 +212,18 @@ fn mutex_arc_example() {
 // - The type system and ownership rules help prevent data races and concurrency bugs.
 // - Send and Sync traits are built into Rust for thread safety guarantees.
 
-pub fn ch16() {
-    println!("-- Basic thread example --");
-    basic_thread_example();
-    println!("-- Join handle example --");
-    join_handle_example();
-    println!("-- Move closure example --");
-    move_closure_example();
-    println!("-- Channel example --");
-    channel_example();
-    println!("-- Multi-producer channel example --");
-    multi_producer_channel_example();
-    println!("-- Mutex/Arc example --");
-    mutex_arc_example();
-    println!("See source for full notes and more concurrency examples in Rust.");
+pub fn ch16(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "-- Basic thread example --")?;
+    basic_thread_example(out)?;
+    writeln!(out, "-- Join handle example --")?;
+    join_handle_example(out)?;
+    writeln!(out, "-- Move closure example --")?;
+    move_closure_example(out)?;
+    writeln!(out, "-- Channel example --")?;
+    channel_example(out)?;
+    writeln!(out, "-- Multi-producer channel example --")?;
+    multi_producer_channel_example(out)?;
+    writeln!(out, "-- Mutex/Arc example --")?;
+    mutex_arc_example(out)?;
+    writeln!(out, "See source for full notes and more concurrency examples in Rust.")
 }
\ No newline at end of file
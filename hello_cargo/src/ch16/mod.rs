@@ -1 +1,29 @@
-pub mod ch16;
\ No newline at end of file
+#![allow(clippy::module_inception)]
+
+pub mod ch16;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch16;
+
+impl Chapter for Ch16 {
+    fn id(&self) -> &'static str {
+        "ch16"
+    }
+
+    fn title(&self) -> &'static str {
+        "Fearless Concurrency"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["threads", "join", "move closures", "channels", "mpsc", "Mutex", "Arc", "Send", "Sync"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch16::ch16(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch16];
@@ -1,174 +1,151 @@
-// Chapter 16: Using Threads, Message Passing, and Shared-State Concurrency - Notes & Examples
+// Chapter 17: Fundamentals of Asynchronous Programming: Async, Await, Futures, and Streams - Notes & Examples
 
 // =============================================================
-// 1. Using Threads to Run Code Simultaneously
+// 1. Futures and the async Syntax
 // =============================================================
 //
-// - Rust uses OS threads (1:1 mapping).
-// - thread::spawn launches a new thread, takes a closure.
-// - Threads may interleave unpredictably. Main thread ending will terminate all spawned threads.
-
-use std::thread;
-use std::time::Duration;
-
-fn basic_thread_example() {
-    thread::spawn(|| {
-        for i in 1..10 {
-            println!("Spawned thread: hi number {i}");
-            thread::sleep(Duration::from_millis(1));
+// - A future is a value that may not be ready yet; it implements std::future::Future.
+// - `async fn` and `async { ... }` blocks compile into state machines that implement Future.
+// - Futures are lazy: nothing runs until something polls them (usually with `.await`).
+// - `.await` is postfix (`fut.await`) and can only be used inside async functions and blocks.
+// - main can't be async: a runtime (executor) has to drive the top-level future.
+//   The book uses the `trpl` crate for that; the examples below use a tiny std-only executor.
+
+use std::cell::RefCell;
+use std::future::{self, Future};
+use std::io::{self, Write};
+use std::pin::{Pin, pin};
+use std::task::{Context, Poll, Waker};
+
+/// Drives a future to completion on the current thread.
+///
+/// It uses a waker that does nothing and simply polls again, which is enough
+/// for futures that never wait on real I/O (like the ones in this chapter).
+pub fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
         }
-    });
-    for i in 1..5 {
-        println!("Main thread: hi number {i}");
-        thread::sleep(Duration::from_millis(1));
     }
-    // Spawned thread may not finish before main ends!
 }
 
-// =============================================================
-// 2. Waiting for Threads: join Handles
-// =============================================================
-//
-// - thread::spawn returns a JoinHandle<T>.
-// - join() will block until the thread finishes.
-
-fn join_handle_example() {
-    let handle = thread::spawn(|| {
-        for i in 1..10 {
-            println!("Spawned thread: hi number {i}");
-            thread::sleep(Duration::from_millis(1));
-        }
-    });
-    for i in 1..5 {
-        println!("Main thread: hi number {i}");
-        thread::sleep(Duration::from_millis(1));
+async fn word_length(word: &str) -> usize {
+    word.len()
+}
+
+// An async fn can await other futures; the caller still gets a future back.
+async fn total_length(words: &[&str]) -> usize {
+    let mut total = 0;
+    for word in words {
+        total += word_length(word).await;
     }
-    handle.join().unwrap(); // Ensures spawned thread completes
+    total
 }
 
 // =============================================================
-// 3. Using move Closures with Threads
+// 2. The Future Trait and Poll
 // =============================================================
 //
-// - move keyword moves ownership of captured variables into the closure/thread.
-
-fn move_closure_example() {
-    let v = vec![1, 2, 3];
-    let handle = thread::spawn(move || {
-        println!("Here's a vector: {:?}", v);
-    });
-    handle.join().unwrap();
+// - trait Future { type Output; fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output>; }
+// - Poll::Ready(value) means done; Poll::Pending means "not yet, wake me later".
+// - A future that returns Pending must arrange for cx.waker() to be called when it can make progress.
+// - Pin<&mut Self> promises the future won't move in memory, which self-referential
+//   async state machines rely on. pin!() and Box::pin() create pinned values.
+
+/// A future that is pending once before completing, handing control back to
+/// the executor so other futures get a turn.
+pub struct YieldNow {
+    yielded: bool,
 }
 
-// =============================================================
-// 4. Message Passing with Channels
-// =============================================================
-//
-// - std::sync::mpsc::channel creates a sender/receiver pair (multiple producer, single consumer).
-// - tx.send(val) moves val into the channel; rx.recv() waits for a value.
-
-use std::sync::mpsc;
-
-fn channel_example() {
-    let (tx, rx) = mpsc::channel();
-    thread::spawn(move || {
-        let vals = vec!["hi", "from", "the", "thread"];
-        for val in vals {
-            tx.send(String::from(val)).unwrap();
-            thread::sleep(Duration::from_millis(500));
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
         }
-    });
-    for received in rx {
-        println!("Got: {received}");
     }
 }
 
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
 // =============================================================
-// 5. Multiple Producers
+// 3. Concurrency with Async: join
 // =============================================================
 //
-// - Clone the sender to have multiple producers.
-
-fn multi_producer_channel_example() {
-    let (tx, rx) = mpsc::channel();
-    let tx1 = tx.clone();
-    thread::spawn(move || {
-        let vals = vec!["one", "two"];
-        for val in vals {
-            tx1.send(String::from(val)).unwrap();
-            thread::sleep(Duration::from_millis(500));
+// - Awaiting one future after another runs them in sequence.
+// - join polls several futures in turn, so their work interleaves on one thread.
+// - Fairness depends on the futures yielding: a future that never returns Pending starves the others.
+
+/// Runs two futures concurrently and returns both outputs.
+pub async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+    let mut a = pin!(a);
+    let mut b = pin!(b);
+    let mut a_output = None;
+    let mut b_output = None;
+
+    future::poll_fn(|cx| {
+        if a_output.is_none()
+            && let Poll::Ready(output) = a.as_mut().poll(cx)
+        {
+            a_output = Some(output);
         }
-    });
-    thread::spawn(move || {
-        let vals = vec!["three", "four"];
-        for val in vals {
-            tx.send(String::from(val)).unwrap();
-            thread::sleep(Duration::from_millis(500));
+        if b_output.is_none()
+            && let Poll::Ready(output) = b.as_mut().poll(cx)
+        {
+            b_output = Some(output);
         }
-    });
-    for received in rx {
-        println!("Got: {received}");
-    }
+        if a_output.is_some() && b_output.is_some() {
+            Poll::Ready(())
+        } else {
+            Poll::Pending
+        }
+    })
+    .await;
+
+    (a_output.unwrap(), b_output.unwrap())
 }
 
-// =============================================================
-// 6. Shared-State Concurrency with Mutex<T> and Arc<T>
-// =============================================================
-//
-// - Mutex<T> provides mutual exclusion: only one thread accesses data at a time.
-// - Arc<T> is an atomic reference-counted pointer for sharing ownership across threads.
-// - Use Arc<Mutex<T>> for shared, mutable state.
-
-use std::sync::{Arc, Mutex};
-
-fn mutex_arc_example() {
-    let counter = Arc::new(Mutex::new(0));
-    let mut handles = vec![];
-    for _ in 0..10 {
-        let counter = Arc::clone(&counter);
-        let handle = thread::spawn(move || {
-            let mut num = counter.lock().unwrap();
-            *num += 1;
-        });
-        handles.push(handle);
+async fn count(name: &str, times: u32, log: &RefCell<Vec<String>>) {
+    for i in 1..=times {
+        log.borrow_mut().push(format!("hi number {i} from the {name} task!"));
+        yield_now().await;
     }
-    for handle in handles {
-        handle.join().unwrap();
-    }
-    println!("Result: {}", *counter.lock().unwrap()); // Should print 10
 }
 
 // =============================================================
-// 7. Send and Sync Traits
+// 4. Streams, Async Traits, and Threads (notes)
 // =============================================================
 //
-// - Send: Type's ownership can be safely transferred between threads.
-// - Sync: Type can be referenced from multiple threads safely.
-// - Most types are Send and Sync unless they contain non-thread-safe primitives (like Rc<T> or RefCell<T>).
-// - Arc<T> and Mutex<T> are thread-safe; Rc<T> and RefCell<T> are NOT.
+// - A stream is an async iterator: a sequence of values that arrive over time (StreamExt::next().await).
+// - Async channels (trpl::channel) work like mpsc, but recv().await yields instead of blocking the thread.
+// - Threads are managed by the OS; futures are tasks managed by a runtime, much cheaper to create.
+// - Use threads for CPU-bound parallel work, async for many waiting (I/O-bound) tasks; they combine well.
+
+pub fn ch17(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "-- Futures are lazy --")?;
+    let sum = async { 1 + 2 }; // nothing has run yet
+    writeln!(out, "async block result: {}", block_on(sum))?;
+
+    writeln!(out, "-- async fn and .await --")?;
+    let total = block_on(total_length(&["hello", "async", "world"]));
+    writeln!(out, "total length: {total}")?;
+
+    writeln!(out, "-- Interleaving two tasks with join --")?;
+    let log = RefCell::new(Vec::new());
+    block_on(join(count("first", 3, &log), count("second", 3, &log)));
+    for line in log.borrow().iter() {
+        writeln!(out, "{line}")?;
+    }
 
-// =============================================================
-// 8. Summary & Best Practices
-// =============================================================
-//
-// - Use threads to run code simultaneously.
-// - Use message passing (channels) to communicate safely between threads.
-// - Use Arc<Mutex<T>> for shared, mutable state.
-// - The type system and ownership rules help prevent data races and concurrency bugs.
-// - Send and Sync traits are built into Rust for thread safety guarantees.
-
-fn main() {
-    println!("-- Basic thread example --");
-    basic_thread_example();
-    println!("-- Join handle example --");
-    join_handle_example();
-    println!("-- Move closure example --");
-    move_closure_example();
-    println!("-- Channel example --");
-    channel_example();
-    println!("-- Multi-producer channel example --");
-    multi_producer_channel_example();
-    println!("-- Mutex/Arc example --");
-    mutex_arc_example();
-    println!("See source for full notes and more concurrency examples in Rust.");
-}
\ No newline at end of file
+    writeln!(out, "See source for notes on futures, Pin, streams and async vs threads.")
+}
@@ -0,0 +1,29 @@
+#![allow(clippy::module_inception)]
+
+pub mod ch17;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch17;
+
+impl Chapter for Ch17 {
+    fn id(&self) -> &'static str {
+        "ch17"
+    }
+
+    fn title(&self) -> &'static str {
+        "Fundamentals of Asynchronous Programming"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["async", "await", "futures", "Future", "Poll", "Pin", "join", "streams"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch17::ch17(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch17];
@@ -1,43 +1,694 @@
-use std::{cmp::Ordering, io};
-use rand::Rng;
+use std::env;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+use std::time::{Duration, Instant, SystemTime};
 
-pub fn ch2(){
-     // we are making here is guessing game
-      let secret_number:i32=rand::thread_rng().gen_range(1..=100);
-      println!("secret number  {}",secret_number);
-    
-  loop{
-    let mut guess_number:String=String::new();
-    
-    io::stdin()
-    .read_line(&mut guess_number)
-    .expect("failed to take input");
+use super::game::{Difficulty, GuessingGame, Outcome, Settings};
+use super::net;
+use super::scores::{self, Loaded, Score, ScoreBoard};
+use super::solver::{self, Benchmark, Solver};
+use crate::cli::{self, ArgsError, Found, Opt};
 
-   let guess_number:i32= match guess_number.trim().parse() {
-    Ok(num)=>num,
-    Err(_)=>continue,
-   };
-   
-        println!("you number {}",guess_number);
+pub fn ch2(out: &mut dyn Write) -> io::Result<()> {
+    // we are making here is guessing game
+    let mut game = GuessingGame::new(&mut rand::thread_rng());
+    play(&mut game, &mut io::stdin().lock(), out).map(|_played| ())
+}
 
+/// How a game played by `play` went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Played {
+    /// The number of attempts, if the game was won.
+    pub won: Option<u32>,
+    /// How many hints were asked for.
+    pub hints: u32,
+}
 
-        match  guess_number.cmp(&secret_number) {
-            Ordering::Less => println!("too less"),
-            Ordering::Equal=>{
-                println!("you won");
-                break;
+/// Plays `game` with guesses read from `input`, one per line, until the game
+/// is over or the input ends.
+///
+/// Lines that aren't numbers, and numbers out of range, are reported without
+/// counting as attempts. A `hint` line suggests the best next guess, from
+/// what the answers so far allow.
+pub fn play(
+    game: &mut GuessingGame,
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> io::Result<Played> {
+    let mut solver = Solver::new(game.settings());
+    let mut hints = 0;
+    write_intro(game.settings(), out)?;
+
+    loop {
+        writeln!(out, "Please input your guess.")?;
+        let mut line = String::new();
+        // read_line returns 0 bytes at end of input; stop instead of looping forever
+        if input.read_line(&mut line)? == 0 {
+            return Ok(Played { won: None, hints });
+        }
+        if line.trim() == "hint" {
+            hints += 1;
+            match (solver.remaining(), solver.next_guess()) {
+                (Some((low, high)), _) if low == high => {
+                    writeln!(out, "Hint: it can only be {low}.")?
+                }
+                (Some((low, high)), Some(next)) => {
+                    writeln!(out, "Hint: it's between {low} and {high}; try {next}.")?
+                }
+                _ => writeln!(out, "Hint: nothing fits the answers so far.")?,
+            }
+            continue;
+        }
+
+        let guess: i32 = match line.trim().parse() {
+            Ok(num) => num,
+            Err(_) => {
+                writeln!(out, "That's not a number: {}", line.trim())?;
+                continue;
+            }
+        };
+        writeln!(out, "You guessed: {guess}")?;
+
+        let outcome = game.guess(guess);
+        solver.update(guess, outcome);
+        match outcome {
+            Outcome::TooLow => writeln!(out, "Too small!")?,
+            Outcome::TooHigh => writeln!(out, "Too big!")?,
+            Outcome::OutOfRange { min, max } => {
+                writeln!(out, "Your guess must be between {min} and {max}.")?;
+                continue;
+            }
+            Outcome::Won { attempts } => {
+                let guesses = if attempts == 1 { "guess" } else { "guesses" };
+                writeln!(out, "You win, in {attempts} {guesses}!")?;
+                return Ok(Played {
+                    won: Some(attempts),
+                    hints,
+                });
+            }
+            Outcome::Lost { secret } => {
+                writeln!(out, "Out of guesses! The number was {secret}.")?;
+                return Ok(Played { won: None, hints });
+            }
+        }
+        match game.attempts_left() {
+            Some(1) => writeln!(out, "Last guess!")?,
+            Some(left) => writeln!(out, "{left} guesses left.")?,
+            None => {}
+        }
+    }
+}
+
+/// The options `hello_cargo guess` understands, in the order `--help` lists them.
+#[rustfmt::skip]
+pub const OPTIONS: &[Opt] = &[
+    Opt::new(Some('d'), "difficulty", "easy (1-50, 10 guesses), normal (1-100, 7) or hard (1-1000, 10)").value("level"),
+    Opt::new(None, "min", "the smallest number the secret can be").value("n"),
+    Opt::new(None, "max", "the largest number the secret can be").value("n"),
+    Opt::new(Some('a'), "attempts", "how many guesses you get").value("n"),
+    Opt::new(None, "unlimited", "guess for as long as it takes"),
+    Opt::new(None, "seed", "pick the secret from a seed, to replay the same game").value("n"),
+    Opt::new(Some('n'), "name", "the player's name for the high scores (default: $USER)").value("player"),
+    Opt::new(None, "scores", "print the high scores instead of playing"),
+    Opt::new(None, "solve", "let the computer play, halving the range every guess"),
+    Opt::new(None, "bench", "let the computer play <games> seeded games and show how many guesses they took").value("games"),
+    Opt::new(Some('h'), "help", "print this help and exit"),
+];
+
+/// The `--help` text, with a line for each of `OPTIONS`.
+pub fn usage() -> String {
+    format!(
+        "Usage: hello_cargo guess [options]\n\
+         \x20      hello_cargo guess serve [options]\n\
+         \x20      hello_cargo guess join [options]\n\n\
+         Plays the guessing game, reading one guess per line; `hint` suggests \
+         the best next guess.\nThe difficulty sets the range and the number of \
+         guesses; the other options change them.\
+         \n\nWins at a preset difficulty, unchanged, unseeded and without hints, go \
+         on the high scores,\nkept in $GUESS_SCORES or \
+         $XDG_DATA_HOME/hello_cargo/scores.tsv.\
+         \n\n`serve` hosts a race to guess the same number over TCP, and `join` \
+         joins one; see their --help.\
+         \n\nOptions:\n{}",
+        cli::describe(OPTIONS)
+    )
+}
+
+/// What `hello_cargo guess` does, besides letting you play.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Play,
+    /// Print the high scores.
+    Scores,
+    /// Watch `Solver` play one game.
+    Solve,
+    /// Have `Solver` play this many games, and print how it did.
+    Bench(u32),
+}
+
+impl Mode {
+    // The option that picks this mode.
+    fn option(self) -> &'static str {
+        match self {
+            Mode::Play => "",
+            Mode::Scores => "--scores",
+            Mode::Solve => "--solve",
+            Mode::Bench(_) => "--bench",
+        }
+    }
+
+    // Only one mode can be picked, though the same one can be picked again.
+    fn switch(self, to: Mode) -> Result<Mode, ArgsError> {
+        if self == Mode::Play || self.option() == to.option() {
+            Ok(to)
+        } else {
+            Err(ArgsError::Conflicts {
+                option: to.option(),
+                with: self.option(),
+            })
+        }
+    }
+}
+
+/// How `hello_cargo guess` was asked to play.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    /// The preset the settings started from.
+    pub difficulty: Difficulty,
+    pub settings: Settings,
+    pub seed: Option<u64>,
+    pub player: Option<String>,
+    pub mode: Mode,
+}
+
+impl Options {
+    /// Parses the options after `args[0]`, the command name. They are applied
+    /// in order, except that `--difficulty` always comes first, so
+    /// `--max 20 -d easy` plays easy up to 20.
+    pub fn parse(args: &[String]) -> Result<Options, ArgsError> {
+        Options::parse_with(OPTIONS, args, |_| Ok(false))
+    }
+
+    /// Like `parse`, for a command with its own `opts`: `extra` sees every
+    /// option first, and returns whether it handled it.
+    pub fn parse_with(
+        opts: &'static [Opt],
+        args: &[String],
+        mut extra: impl FnMut(&Found) -> Result<bool, ArgsError>,
+    ) -> Result<Options, ArgsError> {
+        let parsed = cli::parse(opts, args.get(1..).unwrap_or_default())?;
+        if let Some(extra) = parsed.positional.first() {
+            return Err(ArgsError::UnexpectedArgument(extra.clone()));
+        }
+
+        let mut difficulty = Difficulty::Normal;
+        for found in &parsed.options {
+            if found.opt.long == "difficulty" {
+                let value = found.value.as_deref().unwrap_or_default();
+                difficulty = Difficulty::parse(value).ok_or_else(|| ArgsError::InvalidValue {
+                    option: found.name.clone(),
+                    value: value.to_string(),
+                    expected: "easy, normal or hard",
+                })?;
+            }
+        }
+        let mut settings = difficulty.settings();
+        let mut seed = None;
+        let mut player = None;
+        let mut mode = Mode::Play;
+
+        for found in &parsed.options {
+            // The parser guarantees a value for the options that take one.
+            let value = found.value.as_deref().unwrap_or_default();
+            let invalid = |expected| ArgsError::InvalidValue {
+                option: found.name.clone(),
+                value: value.to_string(),
+                expected,
+            };
+            let number = || value.parse::<i32>().map_err(|_| invalid("a number"));
+            if extra(found)? {
+                continue;
+            }
+
+            match found.opt.long {
+                "help" => return Err(ArgsError::Help),
+                "difficulty" => {}
+                "min" => settings.min = number()?,
+                "max" => settings.max = number()?,
+                "attempts" => match value.parse::<u32>() {
+                    Ok(n) if n > 0 => settings.max_attempts = Some(n),
+                    _ => return Err(invalid("a number of at least 1")),
+                },
+                "unlimited" => settings.max_attempts = None,
+                "seed" => seed = Some(value.parse().map_err(|_| invalid("a number"))?),
+                "name" => match value.trim() {
+                    "" => return Err(invalid("a name")),
+                    name => player = Some(name.to_string()),
+                },
+                "scores" => mode = mode.switch(Mode::Scores)?,
+                "solve" => mode = mode.switch(Mode::Solve)?,
+                "bench" => match value.parse::<u32>() {
+                    Ok(n) if n > 0 => mode = mode.switch(Mode::Bench(n))?,
+                    _ => return Err(invalid("a number of at least 1")),
+                },
+                other => unreachable!("no handler for --{other}"),
             }
-            Ordering::Greater=>println!("too greater"),
-            
         }
+
+        if settings.min > settings.max {
+            return Err(ArgsError::InvalidValue {
+                option: String::from("--max"),
+                value: settings.max.to_string(),
+                expected: "a number no smaller than the minimum",
+            });
+        }
+        Ok(Options {
+            difficulty,
+            settings,
+            seed,
+            player,
+            mode,
+        })
     }
-} 
 
+    /// Whether a win counts for the high scores: only games at a preset
+    /// difficulty, with a secret nobody could know in advance, are comparable.
+    pub fn is_ranked(&self) -> bool {
+        self.seed.is_none() && self.settings == self.difficulty.settings()
+    }
+
+    /// The `--name` given, or `default_player()`.
+    pub fn player(&self) -> String {
+        self.player.clone().unwrap_or_else(default_player)
+    }
 
+    /// A new game as the options describe.
+    pub fn new_game(&self) -> GuessingGame {
+        match self.seed {
+            Some(seed) => GuessingGame::from_seed(self.settings, seed),
+            None => GuessingGame::with_settings(self.settings, &mut rand::thread_rng()),
+        }
+    }
+}
 
+/// The user's login name, or `player` if it isn't known.
+pub fn default_player() -> String {
+    env::var("USER")
+        .ok()
+        .filter(|user| !user.is_empty())
+        .unwrap_or_else(|| String::from("player"))
+}
+
+/// Entry point for `hello_cargo guess [options]`; `args[0]` is the command
+/// name. Exits with 0 if the game was won, 1 if not and 2 for bad options.
+/// Printing the scores or a benchmark exits with 0. `guess serve` and
+/// `guess join` are handed to `net`.
+pub fn guess(args: &[String]) {
+    match args.get(1).map(String::as_str) {
+        Some("serve") => return net::serve_main(&args[1..]),
+        Some("join") => return net::join_main(&args[1..]),
+        _ => {}
+    }
+    let options = Options::parse(args).unwrap_or_else(|err| cli::exit_with(err, usage, "guess"));
+
+    let out = &mut io::stdout();
+    let result = match options.mode {
+        Mode::Scores => show_scores(&options.player(), out).map(|()| true),
+        Mode::Solve => solve(&mut options.new_game(), out),
+        Mode::Bench(games) => bench(&options, games, out).map(|()| true),
+        Mode::Play => play_ranked(&options, out),
+    };
+    match result {
+        Ok(true) => process::exit(0),
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+    }
+}
+
+// Plays over stdin, recording a win if it counts for the high scores.
+fn play_ranked(options: &Options, out: &mut dyn Write) -> io::Result<bool> {
+    let mut game = options.new_game();
+    let started = Instant::now();
+    let played = play(&mut game, &mut io::stdin().lock(), out)?;
+    let won = played
+        .won
+        .filter(|_| played.hints == 0 && options.is_ranked());
+    if let Some(attempts) = won {
+        let score = Score {
+            player: options.player(),
+            difficulty: options.difficulty,
+            attempts,
+            duration: started.elapsed(),
+            timestamp: SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or(Duration::ZERO)
+                .as_secs(),
+        };
+        // The game was won either way; a score that can't be saved is only
+        // worth a warning.
+        if let Err(e) = scores_path().and_then(|path| record(&path, score, out)) {
+            eprintln!("warning: couldn't save the score: {e}");
+        }
+    }
+    Ok(played.won.is_some())
+}
+
+// Lets `Solver` play `game`, narrating; returns whether it won.
+fn solve(game: &mut GuessingGame, out: &mut dyn Write) -> io::Result<bool> {
+    let settings = game.settings();
+    write_range(settings, out)?;
+    let mut result = Ok(());
+    let outcome = solver::play_optimally(game, |guess, outcome| {
+        let answer = match outcome {
+            Outcome::TooLow => String::from("too small."),
+            Outcome::TooHigh => String::from("too big."),
+            Outcome::Won { attempts } => {
+                let guesses = if attempts == 1 { "guess" } else { "guesses" };
+                format!("that's it, in {attempts} {guesses}!")
+            }
+            Outcome::Lost { secret } => format!("out of guesses! The number was {secret}."),
+            Outcome::OutOfRange { min, max } => format!("not between {min} and {max}."),
+        };
+        if result.is_ok() {
+            result = writeln!(out, "Guessing {guess}: {answer}");
+        }
+    });
+    result?;
+    Ok(matches!(outcome, Outcome::Won { .. }))
+}
+
+// Lets `Solver` play `games` games and prints how many guesses they took.
+fn bench(options: &Options, games: u32, out: &mut dyn Write) -> io::Result<()> {
+    let first_seed = options.seed.unwrap_or(0);
+    write_range(options.settings, out)?;
+    writeln!(
+        out,
+        "Played {games} {}, seeded with {first_seed} onwards.",
+        if games == 1 { "game" } else { "games" }
+    )?;
+    Benchmark::run(options.settings, games, first_seed).write_distribution(out)
+}
+
+// `Guess the number between 1 and 100! You have 7 guesses.`
+fn write_intro(settings: Settings, out: &mut dyn Write) -> io::Result<()> {
+    write!(
+        out,
+        "Guess the number between {} and {}!",
+        settings.min, settings.max
+    )?;
+    match settings.max_attempts {
+        Some(max) => writeln!(out, " You have {max} guesses."),
+        None => writeln!(out),
+    }
+}
+
+// The intro, and the best that halving the range can promise.
+fn write_range(settings: Settings, out: &mut dyn Write) -> io::Result<()> {
+    write_intro(settings, out)?;
+    writeln!(
+        out,
+        "Halving the range takes at most {} guesses.",
+        solver::worst_case_attempts(settings.min, settings.max)
+    )
+}
+
+fn scores_path() -> io::Result<PathBuf> {
+    scores::scores_path().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "no place for the high scores; set GUESS_SCORES to a file",
+        )
+    })
+}
+
+// Warns about any high scores that were lost to damage in the file at `path`.
+fn warn_about_damage(path: &Path, loaded: &Loaded) {
+    if loaded.skipped == 0 {
+        return;
+    }
+    let lines = if loaded.skipped == 1 { "line" } else { "lines" };
+    let copied = loaded.backup.as_ref().map_or(String::new(), |backup| {
+        format!("; the damaged file was copied to {}", backup.display())
+    });
+    eprintln!(
+        "warning: skipped {} unreadable {lines} in {}{copied}",
+        loaded.skipped,
+        path.display()
+    );
+}
+
+fn show_scores(player: &str, out: &mut dyn Write) -> io::Result<()> {
+    let path = scores_path()?;
+    let loaded = ScoreBoard::load(&path)?;
+    warn_about_damage(&path, &loaded);
+    loaded.board.write_table(player, out)
+}
+
+// Adds `score` to the high scores at `path` and says whether it's the
+// player's best.
+fn record(path: &Path, score: Score, out: &mut dyn Write) -> io::Result<()> {
+    let loaded = ScoreBoard::record(path, score.clone())?;
+    warn_about_damage(path, &loaded);
+    if loaded.board.personal_best(&score.player, score.difficulty) == Some(&score) {
+        writeln!(out, "New personal best at {}!", score.difficulty.name())?;
+    }
+    Ok(())
+}
 
 // what we learn in chapter 2 is the match which has similar  function like switch in c++,
 // we learn ok,expect method on function.
 // possibility of match uinsg cmp
 // matching of same datatype , here number only
-//concept of let which again assigned to same variable 
\ No newline at end of file
+//concept of let which again assigned to same variable
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn play_lines(game: &mut GuessingGame, input: &str) -> (Option<u32>, String) {
+        let mut out = Vec::new();
+        let played = play(game, &mut input.as_bytes(), &mut out).unwrap();
+        (played.won, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn plays_until_the_secret_is_guessed() {
+        let mut game = GuessingGame::from_seed(Settings::default(), 1);
+        let secret = game.secret();
+        let input = format!(
+            "{}\nfifty\n{}\n{secret}\n{secret}\n",
+            secret - 1,
+            secret + 1
+        );
+
+        let (won, out) = play_lines(&mut game, &input);
+        assert_eq!(won, Some(3));
+        assert!(out.starts_with("Guess the number between 1 and 100!\n"));
+        assert!(out.contains("Too small!\n"));
+        assert!(out.contains("That's not a number: fifty\n"));
+        assert!(out.contains("Too big!\n"));
+        assert!(out.ends_with(&format!("You guessed: {secret}\nYou win, in 3 guesses!\n")));
+    }
+
+    #[test]
+    fn stops_at_the_end_of_input() {
+        let mut game = GuessingGame::from_seed(Settings::default(), 1);
+        assert_eq!(play_lines(&mut game, "").0, None);
+    }
+
+    #[test]
+    fn reports_range_and_running_out() {
+        let settings = Settings {
+            min: 1,
+            max: 3,
+            max_attempts: Some(2),
+        };
+        let mut game = GuessingGame::from_seed(settings, 1);
+        let wrong = if game.secret() == 1 { 2 } else { 1 };
+
+        let (won, out) = play_lines(&mut game, &format!("0\n{wrong}\n{wrong}\n"));
+        assert_eq!(won, None);
+        assert_eq!(
+            out.lines()
+                .filter(|line| !line.starts_with("Too"))
+                .collect::<Vec<_>>(),
+            [
+                "Guess the number between 1 and 3! You have 2 guesses.",
+                "Please input your guess.",
+                "You guessed: 0",
+                "Your guess must be between 1 and 3.",
+                "Please input your guess.",
+                &format!("You guessed: {wrong}"),
+                "Last guess!",
+                "Please input your guess.",
+                &format!("You guessed: {wrong}"),
+                &format!("Out of guesses! The number was {}.", game.secret()),
+            ]
+        );
+    }
+
+    fn options(args: &[&str]) -> Result<Options, ArgsError> {
+        let args: Vec<String> = ["guess"]
+            .iter()
+            .chain(args)
+            .map(|s| s.to_string())
+            .collect();
+        Options::parse(&args)
+    }
+
+    #[test]
+    fn parses_options() {
+        assert_eq!(
+            options(&[]).unwrap().settings,
+            Difficulty::Normal.settings()
+        );
+
+        let parsed = options(&["--max=20", "-d", "easy", "--seed", "9"]).unwrap();
+        assert_eq!(parsed.difficulty, Difficulty::Easy);
+        assert_eq!(
+            parsed.settings,
+            Settings {
+                min: 1,
+                max: 20,
+                max_attempts: Some(10)
+            }
+        );
+        assert_eq!(parsed.seed, Some(9));
+        assert_eq!(
+            parsed.new_game().secret(),
+            GuessingGame::from_seed(parsed.settings, 9).secret()
+        );
+
+        let parsed = options(&["--min", "-10", "--max", "-1", "-a3"]).unwrap();
+        assert_eq!(parsed.settings.range(), -10..=-1);
+        assert_eq!(parsed.settings.max_attempts, Some(3));
+        assert_eq!(
+            options(&["-a", "3", "--unlimited"])
+                .unwrap()
+                .settings
+                .max_attempts,
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_bad_options() {
+        let error = |args: &[&str]| options(args).unwrap_err().to_string();
+        assert_eq!(
+            error(&["-d", "extreme"]),
+            "invalid value `extreme` for -d; expected easy, normal or hard"
+        );
+        assert_eq!(
+            error(&["--min", "50", "--max", "10"]),
+            "invalid value `10` for --max; expected a number no smaller than the minimum"
+        );
+        assert_eq!(
+            error(&["--attempts", "0"]),
+            "invalid value `0` for --attempts; expected a number of at least 1"
+        );
+        assert_eq!(error(&["42"]), "unexpected argument `42`");
+        assert_eq!(options(&["--help"]), Err(ArgsError::Help));
+        assert_eq!(
+            error(&["--name", " "]),
+            "invalid value ` ` for --name; expected a name"
+        );
+    }
+
+    #[test]
+    fn only_preset_unseeded_games_are_ranked() {
+        let parsed = options(&["-d", "hard", "--name", "ann"]).unwrap();
+        assert!(parsed.is_ranked());
+        assert_eq!(parsed.player(), "ann");
+        assert!(!options(&["--seed", "1"]).unwrap().is_ranked());
+        assert!(!options(&["--max", "10"]).unwrap().is_ranked());
+        assert!(!options(&["-d", "easy", "--unlimited"]).unwrap().is_ranked());
+        assert_eq!(options(&["--scores"]).unwrap().mode, Mode::Scores);
+    }
+
+    #[test]
+    fn hints_follow_the_answers() {
+        let mut game = GuessingGame::from_seed(Settings::default(), 1);
+        let secret = game.secret();
+        let mut out = Vec::new();
+        let input = format!("hint\n{}\nhint\n{secret}\n", secret + 1);
+        let played = play(&mut game, &mut input.as_bytes(), &mut out).unwrap();
+        assert_eq!(
+            played,
+            Played {
+                won: Some(2),
+                hints: 2
+            }
+        );
+        let out = String::from_utf8(out).unwrap();
+        assert!(
+            out.contains("Hint: it's between 1 and 100; try 50.\n"),
+            "{out}"
+        );
+        let next = (1 + secret) / 2;
+        assert!(
+            out.contains(&format!("Hint: it's between 1 and {secret}; try {next}.\n"))
+                || secret == 1 && out.contains("Hint: it can only be 1.\n"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn modes_are_exclusive() {
+        assert_eq!(options(&["--bench", "20"]).unwrap().mode, Mode::Bench(20));
+        assert_eq!(options(&["--solve", "--solve"]).unwrap().mode, Mode::Solve);
+        assert_eq!(
+            options(&["--scores", "--bench=3"]).unwrap_err().to_string(),
+            "--bench can't be used with --scores"
+        );
+        assert!(options(&["--bench", "0"]).is_err());
+    }
+
+    #[test]
+    fn the_solver_narrates() {
+        let settings = Settings {
+            min: 1,
+            max: 3,
+            max_attempts: None,
+        };
+        // Seeds until the secret is 3, which takes two guesses.
+        let mut game = (0..)
+            .map(|seed| GuessingGame::from_seed(settings, seed))
+            .find(|game| game.secret() == 3)
+            .unwrap();
+        let mut out = Vec::new();
+        assert!(solve(&mut game, &mut out).unwrap());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "Guess the number between 1 and 3!\n\
+             Halving the range takes at most 2 guesses.\n\
+             Guessing 2: too small.\n\
+             Guessing 3: that's it, in 2 guesses!\n"
+        );
+    }
+    #[test]
+    fn records_personal_bests() {
+        let dir = env::temp_dir().join(format!("guess-record-{}", process::id()));
+        let path = dir.join("scores.tsv");
+        let score = |attempts| Score {
+            player: String::from("bea"),
+            difficulty: Difficulty::Easy,
+            attempts,
+            duration: Duration::from_secs(9),
+            timestamp: 1_792_108_800,
+        };
+        let mut out = Vec::new();
+        record(&path, score(4), &mut out).unwrap();
+        record(&path, score(5), &mut out).unwrap();
+        record(&path, score(3), &mut out).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "New personal best at easy!\nNew personal best at easy!\n"
+        );
+    }
+}
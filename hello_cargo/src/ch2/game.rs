@@ -0,0 +1,303 @@
+// The guessing game from chapter 2 as a state machine, with no I/O of its
+// own: a game picks a secret number and answers guesses until one is right
+// or the attempts run out. Frontends read guesses from wherever they like and
+// print the outcomes, and tests can play a game whose secret they know by
+// seeding its RNG.
+
+use std::cmp::Ordering;
+use std::ops::RangeInclusive;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+use crate::ch9::ch9_3::Guess;
+
+/// The range the secret is picked from and how many guesses a player gets.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub min: i32,
+    pub max: i32,
+    /// `None` for as many guesses as it takes.
+    pub max_attempts: Option<u32>,
+}
+
+/// The chapter's original game: 1 to 100, with unlimited guesses.
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings {
+            min: 1,
+            max: 100,
+            max_attempts: None,
+        }
+    }
+}
+
+impl Settings {
+    pub fn range(&self) -> RangeInclusive<i32> {
+        self.min..=self.max
+    }
+}
+
+/// Preset settings. Normal and hard give exactly as many guesses as halving
+/// the range every time needs, so they can always be won, but only just.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Normal, Difficulty::Hard];
+
+    /// Parses `easy`, `normal` or `hard`.
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        Difficulty::ALL
+            .into_iter()
+            .find(|difficulty| difficulty.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+        }
+    }
+
+    pub fn settings(self) -> Settings {
+        let (max, max_attempts) = match self {
+            Difficulty::Easy => (50, 10),
+            Difficulty::Normal => (100, 7),
+            Difficulty::Hard => (1000, 10),
+        };
+        Settings {
+            min: 1,
+            max,
+            max_attempts: Some(max_attempts),
+        }
+    }
+}
+
+/// What a guess turned out to be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    TooLow,
+    TooHigh,
+    /// The guess was the secret, after `attempts` guesses in all.
+    Won {
+        attempts: u32,
+    },
+    /// The last allowed guess was wrong; the game is over.
+    Lost {
+        secret: i32,
+    },
+    /// The guess was outside the game's range. It doesn't count as an attempt.
+    OutOfRange {
+        min: i32,
+        max: i32,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum State {
+    Playing,
+    Won,
+    Lost,
+}
+
+/// One game: a secret number and the guesses made so far.
+#[derive(Debug, Clone)]
+pub struct GuessingGame {
+    settings: Settings,
+    secret: i32,
+    attempts: u32,
+    state: State,
+}
+
+impl GuessingGame {
+    /// Starts a game with the default settings and a secret picked by `rng`.
+    pub fn new(rng: &mut impl Rng) -> GuessingGame {
+        GuessingGame::with_settings(Settings::default(), rng)
+    }
+
+    /// Starts a game with `settings` and a secret picked from their range by `rng`.
+    ///
+    /// # Panics
+    ///
+    /// If the range is empty, with `min` greater than `max`.
+    pub fn with_settings(settings: Settings, rng: &mut impl Rng) -> GuessingGame {
+        assert!(
+            settings.min <= settings.max,
+            "empty range {}..={}",
+            settings.min,
+            settings.max
+        );
+        GuessingGame {
+            settings,
+            secret: rng.gen_range(settings.range()),
+            attempts: 0,
+            state: State::Playing,
+        }
+    }
+
+    /// Starts a game whose secret depends only on `seed` and `settings`, for
+    /// repeatable games.
+    pub fn from_seed(settings: Settings, seed: u64) -> GuessingGame {
+        GuessingGame::with_settings(settings, &mut StdRng::seed_from_u64(seed))
+    }
+
+    /// Compares `guess` with the secret and counts it as an attempt, unless
+    /// it's out of range.
+    ///
+    /// Once the game is over, further guesses aren't counted and keep
+    /// returning the final outcome.
+    pub fn guess(&mut self, guess: i32) -> Outcome {
+        match self.state {
+            State::Playing => {}
+            State::Won => {
+                return Outcome::Won {
+                    attempts: self.attempts,
+                };
+            }
+            State::Lost => {
+                return Outcome::Lost {
+                    secret: self.secret,
+                };
+            }
+        }
+        let guess = match Guess::in_range(guess, self.settings.range()) {
+            Ok(guess) => guess,
+            Err(e) => {
+                return Outcome::OutOfRange {
+                    min: e.min,
+                    max: e.max,
+                };
+            }
+        };
+
+        self.attempts += 1;
+        let outcome = match guess.value().cmp(&self.secret) {
+            Ordering::Less => Outcome::TooLow,
+            Ordering::Greater => Outcome::TooHigh,
+            Ordering::Equal => {
+                self.state = State::Won;
+                return Outcome::Won {
+                    attempts: self.attempts,
+                };
+            }
+        };
+        if self.attempts_left() == Some(0) {
+            self.state = State::Lost;
+            return Outcome::Lost {
+                secret: self.secret,
+            };
+        }
+        outcome
+    }
+
+    pub fn settings(&self) -> Settings {
+        self.settings
+    }
+
+    /// How many guesses have been counted.
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    /// How many more guesses can be made, if they are limited.
+    pub fn attempts_left(&self) -> Option<u32> {
+        self.settings
+            .max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    /// Whether the secret has been guessed.
+    pub fn is_won(&self) -> bool {
+        self.state == State::Won
+    }
+
+    /// Whether the game has been won or lost.
+    pub fn is_over(&self) -> bool {
+        self.state != State::Playing
+    }
+
+    /// The secret, for showing once the game is over; guessing with it wins.
+    pub fn secret(&self) -> i32 {
+        self.secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn seeded_games_are_repeatable() {
+        let settings = Difficulty::Hard.settings();
+        let secret = GuessingGame::from_seed(settings, 7).secret();
+        assert!(settings.range().contains(&secret));
+        assert_eq!(GuessingGame::from_seed(settings, 7).secret(), secret);
+    }
+
+    #[test]
+    fn guesses_are_compared_and_counted() {
+        let mut game = GuessingGame::from_seed(Settings::default(), 7);
+        let secret = game.secret();
+        assert_eq!(game.guess(secret - 1), Outcome::TooLow);
+        assert_eq!(game.guess(secret + 1), Outcome::TooHigh);
+        assert!(!game.is_won());
+        assert_eq!(game.guess(secret), Outcome::Won { attempts: 3 });
+        assert!(game.is_won());
+
+        // The game is over; nothing more is counted.
+        assert_eq!(game.guess(secret + 1), Outcome::Won { attempts: 3 });
+        assert_eq!(game.attempts(), 3);
+    }
+
+    #[test]
+    fn out_of_range_guesses_are_rejected_without_counting() {
+        let settings = Settings {
+            min: -5,
+            max: 5,
+            max_attempts: None,
+        };
+        let mut game = GuessingGame::from_seed(settings, 3);
+        assert!((-5..=5).contains(&game.secret()));
+        assert_eq!(game.guess(6), Outcome::OutOfRange { min: -5, max: 5 });
+        assert_eq!(game.guess(-6), Outcome::OutOfRange { min: -5, max: 5 });
+        assert_eq!(game.attempts(), 0);
+    }
+
+    #[test]
+    fn running_out_of_attempts_loses() {
+        let settings = Settings {
+            max_attempts: Some(2),
+            ..Settings::default()
+        };
+        let mut game = GuessingGame::from_seed(settings, 7);
+        let secret = game.secret();
+        let wrong = if secret == 1 { 2 } else { 1 };
+        assert_ne!(game.guess(wrong), Outcome::Lost { secret });
+        assert_eq!(game.attempts_left(), Some(1));
+        assert_eq!(game.guess(wrong), Outcome::Lost { secret });
+        assert!(game.is_over() && !game.is_won());
+        assert_eq!(game.guess(secret), Outcome::Lost { secret });
+        assert_eq!(game.attempts(), 2);
+
+        // Winning with the last attempt still wins.
+        let mut game = GuessingGame::from_seed(settings, 7);
+        game.guess(wrong);
+        assert_eq!(game.guess(secret), Outcome::Won { attempts: 2 });
+    }
+
+    #[test]
+    fn difficulties() {
+        assert_eq!(Difficulty::parse("hard"), Some(Difficulty::Hard));
+        assert_eq!(Difficulty::parse("Hard"), None);
+        for difficulty in Difficulty::ALL {
+            assert_eq!(Difficulty::parse(difficulty.name()), Some(difficulty));
+        }
+        assert_eq!(Difficulty::Normal.settings().range(), 1..=100);
+    }
+}
@@ -1 +1,37 @@
-pub mod ch2;
\ No newline at end of file
+#![allow(clippy::module_inception)]
+
+pub mod ch2;
+pub mod game;
+pub mod net;
+pub mod scores;
+pub mod solver;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch2;
+
+impl Chapter for Ch2 {
+    fn id(&self) -> &'static str {
+        "ch2"
+    }
+
+    fn title(&self) -> &'static str {
+        "Programming a Guessing Game"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["stdin", "match", "Ordering", "rand", "loop"]
+    }
+
+    fn interactive(&self) -> bool {
+        true
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch2::ch2(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch2];
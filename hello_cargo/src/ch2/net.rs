@@ -0,0 +1,788 @@
+// The guessing game as a race over TCP: `guess serve` picks one secret, and
+// every player who connects with `guess join` tries to guess it first, with
+// their own guesses and their own answers.
+//
+// The server is put together from chapter 16's pieces: a thread accepts
+// connections, one thread per connection reads its lines, and they all send
+// what happens down one channel to the thread running the race, which is
+// the only one that touches the game, so guesses are settled in the order
+// they arrive. Writes to a player give up after `WRITE_TIMEOUT`, and a
+// player whose write fails is dropped, so one client that stops reading
+// can't hold up the race for everyone else.
+//
+// The protocol is a line per message. Clients send
+//
+//   HELLO <name>        join the race; the first thing to send
+//   GUESS <n>
+//   QUIT
+//
+// and the server answers with
+//
+//   WELCOME <min> <max> <guesses|unlimited>
+//   WAIT <players> <needed>         the race hasn't started yet
+//   START                           it has; guess away
+//   LOW <n> | HIGH <n>              the answer to your guess
+//   RANGE <min> <max>               your guess was out of range
+//   OUT                             you have no guesses left
+//   ERROR <message>
+//
+// and tells everyone `JOINED <name>`, `LEFT <name>`, and at the end either
+// `WIN <guesses> <secret> <name>` or `OVER <secret>` if nobody guessed it.
+// Names go last, so they can have spaces.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::process;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use super::ch2::{self as game_command, Options};
+use super::game::{GuessingGame, Outcome, Settings};
+use crate::cli::{self, ArgsError, Opt};
+
+/// Where `guess serve` listens and `guess join` connects unless told otherwise.
+pub const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+
+/// How long the race waits on a player who isn't reading before dropping them.
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How a race ended.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Ending {
+    Won {
+        player: String,
+        attempts: u32,
+        secret: i32,
+    },
+    /// Every player ran out of guesses or left.
+    NobodyWon { secret: i32 },
+}
+
+// What the connection threads tell the race.
+enum Event {
+    Connected(usize, TcpStream),
+    Line(usize, String),
+    Disconnected(usize),
+}
+
+/// A race waiting for players, bound to an address.
+pub struct Server {
+    listener: TcpListener,
+    game: GuessingGame,
+    players: usize,
+}
+
+impl Server {
+    /// Listens on `addr` for a race of `game`, which starts once `players`
+    /// players have joined. Every player gets a copy of `game`, so the same
+    /// secret and as many guesses.
+    pub fn bind(
+        addr: impl ToSocketAddrs,
+        game: GuessingGame,
+        players: usize,
+    ) -> io::Result<Server> {
+        Ok(Server {
+            listener: TcpListener::bind(addr)?,
+            game,
+            players: players.max(1),
+        })
+    }
+
+    /// The address the server is listening on, with the port picked if it was 0.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Runs the race to the end, writing what happens to `log`.
+    pub fn run(self, log: &mut dyn Write) -> io::Result<Ending> {
+        let addr = self.local_addr()?;
+        let (tx, rx) = mpsc::channel();
+        let listener = self.listener;
+        thread::spawn(move || accept(listener, tx));
+
+        let mut race = Race {
+            template: self.game,
+            needed: self.players,
+            started: false,
+            players: BTreeMap::new(),
+        };
+        let mut ending = None;
+        for event in &rx {
+            ending = race.handle(event, log)?;
+            if ending.is_some() {
+                break;
+            }
+        }
+        for player in race.players.values() {
+            let _ = player.stream.shutdown(Shutdown::Both);
+        }
+        // The accept thread notices the race is over at its next connection.
+        drop(rx);
+        let _ = TcpStream::connect(addr);
+        ending.ok_or_else(|| io::Error::other("the connections stopped"))
+    }
+}
+
+// Accepts connections, with a thread for each that sends its lines as events,
+// until the race stops listening.
+fn accept(listener: TcpListener, tx: mpsc::Sender<Event>) {
+    for (id, stream) in listener.incoming().enumerate() {
+        let Ok(stream) = stream else { continue };
+        if stream.set_write_timeout(Some(WRITE_TIMEOUT)).is_err() {
+            continue;
+        }
+        let Ok(reader) = stream.try_clone() else {
+            continue;
+        };
+        if tx.send(Event::Connected(id, stream)).is_err() {
+            return;
+        }
+        let tx = tx.clone();
+        thread::spawn(move || {
+            for line in BufReader::new(reader).lines() {
+                let Ok(line) = line else { break };
+                if tx.send(Event::Line(id, line)).is_err() {
+                    return;
+                }
+            }
+            let _ = tx.send(Event::Disconnected(id));
+        });
+    }
+}
+
+struct Player {
+    /// `None` until the player says `HELLO`.
+    name: Option<String>,
+    stream: TcpStream,
+    game: GuessingGame,
+}
+
+impl Player {
+    // A player who has gone away, or stopped reading until the write timed
+    // out, is cut off here; their reader thread then sees the end of the
+    // stream and says they've left.
+    fn send(&mut self, message: &str) {
+        if writeln!(self.stream, "{message}").is_err() {
+            let _ = self.stream.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+struct Race {
+    template: GuessingGame,
+    needed: usize,
+    started: bool,
+    players: BTreeMap<usize, Player>,
+}
+
+impl Race {
+    fn handle(&mut self, event: Event, log: &mut dyn Write) -> io::Result<Option<Ending>> {
+        match event {
+            Event::Connected(id, stream) => {
+                let game = self.template.clone();
+                self.players.insert(
+                    id,
+                    Player {
+                        name: None,
+                        stream,
+                        game,
+                    },
+                );
+                Ok(None)
+            }
+            Event::Line(id, line) => self.command(id, line.trim(), log),
+            Event::Disconnected(id) => {
+                if let Some(Player {
+                    name: Some(name), ..
+                }) = self.players.remove(&id)
+                {
+                    writeln!(log, "{name} left.")?;
+                    self.broadcast(&format!("LEFT {name}"));
+                }
+                self.check_over(log)
+            }
+        }
+    }
+
+    fn command(
+        &mut self,
+        id: usize,
+        line: &str,
+        log: &mut dyn Write,
+    ) -> io::Result<Option<Ending>> {
+        let (verb, arg) = line
+            .split_once(' ')
+            .map_or((line, ""), |(verb, arg)| (verb, arg.trim()));
+        let Some(player) = self.players.get_mut(&id) else {
+            return Ok(None);
+        };
+        match (verb, player.name.clone()) {
+            ("QUIT", _) => {
+                // The reader thread sees the end of the stream and says so.
+                let _ = player.stream.shutdown(Shutdown::Both);
+                Ok(None)
+            }
+            ("HELLO", None) => self.hello(id, arg, log),
+            ("HELLO", Some(_)) => {
+                player.send("ERROR you have already joined");
+                Ok(None)
+            }
+            (_, None) => {
+                player.send("ERROR say HELLO <name> first");
+                Ok(None)
+            }
+            ("GUESS", Some(name)) => self.guess(id, &name, arg, log),
+            (_, Some(_)) => {
+                player.send(&format!("ERROR unknown command: {verb}"));
+                Ok(None)
+            }
+        }
+    }
+
+    fn names(&self) -> impl Iterator<Item = &str> {
+        self.players
+            .values()
+            .filter_map(|player| player.name.as_deref())
+    }
+
+    fn broadcast(&mut self, message: &str) {
+        for player in self.players.values_mut() {
+            if player.name.is_some() {
+                player.send(message);
+            }
+        }
+    }
+
+    fn hello(&mut self, id: usize, name: &str, log: &mut dyn Write) -> io::Result<Option<Ending>> {
+        let taken = self.names().any(|other| other == name);
+        let player = self.players.get_mut(&id).expect("the player is connected");
+        if name.is_empty() {
+            player.send("ERROR say HELLO <name>");
+            return Ok(None);
+        }
+        if taken {
+            player.send(&format!("ERROR the name {name} is taken"));
+            return Ok(None);
+        }
+
+        let settings = player.game.settings();
+        let guesses = settings
+            .max_attempts
+            .map_or(String::from("unlimited"), |max| max.to_string());
+        player.send(&format!(
+            "WELCOME {} {} {guesses}",
+            settings.min, settings.max
+        ));
+        self.broadcast(&format!("JOINED {name}"));
+        let player = self.players.get_mut(&id).expect("the player is connected");
+        player.name = Some(name.to_string());
+
+        if self.started {
+            player.send("START");
+            writeln!(log, "{name} joined.")?;
+            return Ok(None);
+        }
+        let joined = self.names().count();
+        writeln!(log, "{name} joined ({joined} of {}).", self.needed)?;
+        if joined >= self.needed {
+            self.started = true;
+            writeln!(log, "The race is on!")?;
+            self.broadcast("START");
+        }
+        Ok(None)
+    }
+
+    fn guess(
+        &mut self,
+        id: usize,
+        name: &str,
+        guess: &str,
+        log: &mut dyn Write,
+    ) -> io::Result<Option<Ending>> {
+        let joined = self.names().count();
+        let player = self.players.get_mut(&id).expect("the player is connected");
+        if !self.started {
+            player.send(&format!("WAIT {joined} {}", self.needed));
+            return Ok(None);
+        }
+        let Ok(guess) = guess.parse::<i32>() else {
+            player.send(&format!("ERROR not a number: {guess}"));
+            return Ok(None);
+        };
+        if player.game.is_over() {
+            player.send("OUT");
+            return Ok(None);
+        }
+
+        match player.game.guess(guess) {
+            Outcome::TooLow => {
+                writeln!(log, "{name} guessed {guess}: too small.")?;
+                player.send(&format!("LOW {guess}"));
+            }
+            Outcome::TooHigh => {
+                writeln!(log, "{name} guessed {guess}: too big.")?;
+                player.send(&format!("HIGH {guess}"));
+            }
+            Outcome::OutOfRange { min, max } => player.send(&format!("RANGE {min} {max}")),
+            Outcome::Lost { .. } => {
+                writeln!(log, "{name} guessed {guess} and is out of guesses.")?;
+                player.send("OUT");
+                return self.check_over(log);
+            }
+            Outcome::Won { attempts } => {
+                let secret = player.game.secret();
+                let guesses = if attempts == 1 { "guess" } else { "guesses" };
+                writeln!(
+                    log,
+                    "{name} won, in {attempts} {guesses}. The number was {secret}."
+                )?;
+                self.broadcast(&format!("WIN {attempts} {secret} {name}"));
+                return Ok(Some(Ending::Won {
+                    player: name.to_string(),
+                    attempts,
+                    secret,
+                }));
+            }
+        }
+        Ok(None)
+    }
+
+    // Once the race has started, it's over when nobody in it can still guess.
+    fn check_over(&mut self, log: &mut dyn Write) -> io::Result<Option<Ending>> {
+        let playing = self
+            .players
+            .values()
+            .any(|player| player.name.is_some() && !player.game.is_over());
+        if !self.started || playing {
+            return Ok(None);
+        }
+        let secret = self.template.secret();
+        writeln!(log, "Nobody guessed it. The number was {secret}.")?;
+        self.broadcast(&format!("OVER {secret}"));
+        Ok(Some(Ending::NobodyWon { secret }))
+    }
+}
+
+/// Joins the race at `addr` as `name`, sending the guesses read from `input`,
+/// one per line, and writing what the server says to `out` until the race
+/// is over. Returns whether `name` won.
+pub fn join(
+    addr: impl ToSocketAddrs,
+    name: &str,
+    input: Box<dyn BufRead + Send>,
+    out: &mut dyn Write,
+) -> io::Result<bool> {
+    let mut stream = TcpStream::connect(addr)?;
+    writeln!(stream, "HELLO {name}")?;
+
+    // Guesses go out as they're typed, while answers come back on this thread.
+    let mut writer = stream.try_clone()?;
+    thread::spawn(move || {
+        for line in input.lines() {
+            let Ok(line) = line else { break };
+            let message = match line.trim() {
+                "" => continue,
+                "quit" => String::from("QUIT"),
+                guess => format!("GUESS {guess}"),
+            };
+            if writeln!(writer, "{message}").is_err() {
+                break;
+            }
+        }
+    });
+
+    let mut welcomed = false;
+    let mut max_attempts = None;
+    let mut attempts = 0;
+    for line in BufReader::new(stream).lines() {
+        let line = line?;
+        let (verb, rest) = line.split_once(' ').unwrap_or((&line, ""));
+        let args: Vec<&str> = rest.splitn(3, ' ').collect();
+        match (verb, args.as_slice()) {
+            ("WELCOME", [min, max, guesses]) => {
+                welcomed = true;
+                write!(
+                    out,
+                    "Joined as {name}. Guess the number between {min} and {max}!"
+                )?;
+                max_attempts = guesses.parse::<u32>().ok();
+                match max_attempts {
+                    Some(max) => writeln!(out, " You have {max} guesses.")?,
+                    None => writeln!(out)?,
+                }
+            }
+            ("ERROR", _) if !welcomed => return Err(io::Error::other(rest.to_string())),
+            ("ERROR", _) => writeln!(out, "Error: {rest}")?,
+            ("WAIT", [joined, needed]) => writeln!(
+                out,
+                "The race hasn't started; {joined} of {needed} players are here."
+            )?,
+            ("START", _) => writeln!(out, "The race is on! Please input your guess.")?,
+            ("JOINED", _) => writeln!(out, "{rest} joined.")?,
+            ("LEFT", _) => writeln!(out, "{rest} left.")?,
+            ("LOW" | "HIGH", [guess]) => {
+                let answer = if verb == "LOW" { "small" } else { "big" };
+                writeln!(out, "{guess} is too {answer}!")?;
+                attempts += 1;
+                match max_attempts.map(|max| max.saturating_sub(attempts)) {
+                    Some(1) => writeln!(out, "Last guess!")?,
+                    Some(left) => writeln!(out, "{left} guesses left.")?,
+                    None => {}
+                }
+            }
+            ("RANGE", [min, max]) => writeln!(out, "Your guess must be between {min} and {max}.")?,
+            ("OUT", _) => writeln!(out, "Out of guesses! Waiting for the others to finish.")?,
+            ("WIN", [attempts, secret, winner]) => {
+                let guesses = if *attempts == "1" { "guess" } else { "guesses" };
+                if *winner == name {
+                    writeln!(out, "You win, in {attempts} {guesses}!")?;
+                } else {
+                    writeln!(
+                        out,
+                        "{winner} won, in {attempts} {guesses}. The number was {secret}."
+                    )?;
+                }
+                return Ok(*winner == name);
+            }
+            ("OVER", [secret]) => {
+                writeln!(out, "Nobody guessed it. The number was {secret}.")?;
+                return Ok(false);
+            }
+            _ => writeln!(out, "{line}")?,
+        }
+    }
+    Err(io::Error::new(
+        io::ErrorKind::UnexpectedEof,
+        "the server closed the connection",
+    ))
+}
+
+/// The options `guess serve` understands.
+#[rustfmt::skip]
+pub const SERVE_OPTIONS: &[Opt] = &[
+    Opt::new(Some('d'), "difficulty", "easy (1-50, 10 guesses), normal (1-100, 7) or hard (1-1000, 10)").value("level"),
+    Opt::new(None, "min", "the smallest number the secret can be").value("n"),
+    Opt::new(None, "max", "the largest number the secret can be").value("n"),
+    Opt::new(Some('a'), "attempts", "how many guesses each player gets").value("n"),
+    Opt::new(None, "unlimited", "guess for as long as it takes"),
+    Opt::new(None, "seed", "pick the secret from a seed, to replay the same game").value("n"),
+    Opt::new(Some('l'), "listen", "the address to listen on (default: 127.0.0.1:7878)").value("addr"),
+    Opt::new(Some('p'), "players", "how many players to wait for before starting (default: 1)").value("n"),
+    Opt::new(Some('h'), "help", "print this help and exit"),
+];
+
+/// The options `guess join` understands.
+#[rustfmt::skip]
+pub const JOIN_OPTIONS: &[Opt] = &[
+    Opt::new(Some('c'), "connect", "the server's address (default: 127.0.0.1:7878)").value("addr"),
+    Opt::new(Some('n'), "name", "your name in the race (default: $USER)").value("player"),
+    Opt::new(Some('h'), "help", "print this help and exit"),
+];
+
+/// The `--help` text for `guess serve`.
+pub fn serve_usage() -> String {
+    format!(
+        "Usage: hello_cargo guess serve [options]\n\n\
+         Hosts a race to guess one secret number over TCP, for players who \
+         run `guess join`.\nIt ends when someone guesses it, or when nobody \
+         has guesses left.\n\nOptions:\n{}",
+        cli::describe(SERVE_OPTIONS)
+    )
+}
+
+/// The `--help` text for `guess join`.
+pub fn join_usage() -> String {
+    format!(
+        "Usage: hello_cargo guess join [options]\n\n\
+         Joins a race hosted by `guess serve`, reading one guess per line; \
+         `quit` leaves.\n\nOptions:\n{}",
+        cli::describe(JOIN_OPTIONS)
+    )
+}
+
+/// Parses `guess serve`'s options after `args[0]`, `serve`: the game, the
+/// address to listen on and how many players to wait for.
+pub fn parse_serve_args(args: &[String]) -> Result<(Options, String, usize), ArgsError> {
+    let mut addr = String::from(DEFAULT_ADDR);
+    let mut players = 1;
+    let options = Options::parse_with(SERVE_OPTIONS, args, |found| {
+        let value = found.value.as_deref().unwrap_or_default();
+        match found.opt.long {
+            "listen" => addr = value.to_string(),
+            "players" => match value.parse::<usize>() {
+                Ok(n) if n > 0 => players = n,
+                _ => {
+                    return Err(ArgsError::InvalidValue {
+                        option: found.name.clone(),
+                        value: value.to_string(),
+                        expected: "a number of at least 1",
+                    });
+                }
+            },
+            _ => return Ok(false),
+        }
+        Ok(true)
+    })?;
+    Ok((options, addr, players))
+}
+
+/// Parses `guess join`'s options after `args[0]`, `join`: the address and
+/// the player's name.
+pub fn parse_join_args(args: &[String]) -> Result<(String, String), ArgsError> {
+    let parsed = cli::parse(JOIN_OPTIONS, args.get(1..).unwrap_or_default())?;
+    if let Some(extra) = parsed.positional.first() {
+        return Err(ArgsError::UnexpectedArgument(extra.clone()));
+    }
+    let mut addr = String::from(DEFAULT_ADDR);
+    let mut name = None;
+    for found in parsed.options {
+        let value = found.value.clone().unwrap_or_default();
+        match found.opt.long {
+            "help" => return Err(ArgsError::Help),
+            "connect" => addr = value,
+            "name" => match value.trim() {
+                "" => {
+                    return Err(ArgsError::InvalidValue {
+                        option: found.name,
+                        value,
+                        expected: "a name",
+                    });
+                }
+                trimmed => name = Some(trimmed.to_string()),
+            },
+            other => unreachable!("no handler for --{other}"),
+        }
+    }
+    Ok((addr, name.unwrap_or_else(game_command::default_player)))
+}
+
+/// Entry point for `hello_cargo guess serve [options]`; `args[0]` is
+/// `serve`. Exits with 0 if someone won, 1 if nobody did and 2 on errors.
+pub fn serve_main(args: &[String]) {
+    let (options, addr, players) = parse_serve_args(args)
+        .unwrap_or_else(|err| cli::exit_with(err, serve_usage, "guess serve"));
+    let game = options.new_game();
+    let Settings { min, max, .. } = game.settings();
+
+    let result = Server::bind(&addr, game, players).and_then(|server| {
+        let players = if players == 1 { "player" } else { "players" };
+        println!(
+            "Serving a race between {min} and {max} on {}; waiting for {} {players}.",
+            server.local_addr()?,
+            server.players
+        );
+        server.run(&mut io::stdout())
+    });
+    match result {
+        Ok(Ending::Won { .. }) => process::exit(0),
+        Ok(Ending::NobodyWon { .. }) => process::exit(1),
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+    }
+}
+
+/// Entry point for `hello_cargo guess join [options]`; `args[0]` is `join`.
+/// Exits with 0 if you won, 1 if not and 2 on errors.
+pub fn join_main(args: &[String]) {
+    let (addr, name) =
+        parse_join_args(args).unwrap_or_else(|err| cli::exit_with(err, join_usage, "guess join"));
+    let input = Box::new(BufReader::new(io::stdin()));
+    match join(addr.as_str(), &name, input, &mut io::stdout()) {
+        Ok(true) => process::exit(0),
+        Ok(false) => process::exit(1),
+        Err(e) => {
+            eprintln!("Application error: {e}");
+            process::exit(2);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A race on a free port, run on its own thread.
+    fn serve(
+        settings: Settings,
+        players: usize,
+    ) -> (SocketAddr, thread::JoinHandle<(Ending, String)>) {
+        let server =
+            Server::bind("127.0.0.1:0", GuessingGame::from_seed(settings, 5), players).unwrap();
+        let addr = server.local_addr().unwrap();
+        let handle = thread::spawn(move || {
+            let mut log = Vec::new();
+            let ending = server.run(&mut log).unwrap();
+            (ending, String::from_utf8(log).unwrap())
+        });
+        (addr, handle)
+    }
+
+    struct Client {
+        stream: TcpStream,
+        lines: io::Lines<BufReader<TcpStream>>,
+    }
+
+    impl Client {
+        fn connect(addr: SocketAddr) -> Client {
+            let stream = TcpStream::connect(addr).unwrap();
+            stream
+                .set_read_timeout(Some(Duration::from_secs(10)))
+                .unwrap();
+            let lines = BufReader::new(stream.try_clone().unwrap()).lines();
+            Client { stream, lines }
+        }
+
+        fn send(&mut self, message: &str) {
+            writeln!(self.stream, "{message}").unwrap();
+        }
+
+        fn expect(&mut self, message: &str) {
+            assert_eq!(self.lines.next().unwrap().unwrap(), message);
+        }
+    }
+
+    #[test]
+    fn first_to_guess_wins() {
+        let settings = Settings::default();
+        let secret = GuessingGame::from_seed(settings, 5).secret();
+        let (addr, server) = serve(settings, 2);
+
+        let mut ann = Client::connect(addr);
+        ann.send("GUESS 50");
+        ann.expect("ERROR say HELLO <name> first");
+        ann.send("HELLO ann");
+        ann.expect("WELCOME 1 100 unlimited");
+        ann.send("GUESS 50");
+        ann.expect("WAIT 1 2");
+
+        let mut bob = Client::connect(addr);
+        bob.send("HELLO ann");
+        bob.expect("ERROR the name ann is taken");
+        bob.send("HELLO bob smith");
+        bob.expect("WELCOME 1 100 unlimited");
+        ann.expect("JOINED bob smith");
+        ann.expect("START");
+        bob.expect("START");
+
+        // Each player hears only the answers to their own guesses.
+        ann.send(&format!("GUESS {}", secret - 1));
+        ann.expect(&format!("LOW {}", secret - 1));
+        bob.send(&format!("GUESS {}", secret + 1));
+        bob.expect(&format!("HIGH {}", secret + 1));
+        bob.send("GUESS lots");
+        bob.expect("ERROR not a number: lots");
+        bob.send("GUESS 101");
+        bob.expect("RANGE 1 100");
+        bob.send(&format!("GUESS {secret}"));
+        bob.expect(&format!("WIN 2 {secret} bob smith"));
+        ann.expect(&format!("WIN 2 {secret} bob smith"));
+        assert!(ann.lines.next().is_none());
+
+        let (ending, log) = server.join().unwrap();
+        assert_eq!(
+            ending,
+            Ending::Won {
+                player: String::from("bob smith"),
+                attempts: 2,
+                secret
+            }
+        );
+        assert!(
+            log.starts_with("ann joined (1 of 2).\nbob smith joined (2 of 2).\nThe race is on!\n")
+        );
+        assert!(log.ends_with(&format!(
+            "bob smith won, in 2 guesses. The number was {secret}.\n"
+        )));
+    }
+
+    #[test]
+    fn nobody_wins_when_everyone_is_out() {
+        let settings = Settings {
+            min: 1,
+            max: 3,
+            max_attempts: Some(1),
+        };
+        let secret = GuessingGame::from_seed(settings, 5).secret();
+        let wrong = if secret == 1 { 2 } else { 1 };
+        let (addr, server) = serve(settings, 1);
+
+        let mut ann = Client::connect(addr);
+        ann.send("HELLO ann");
+        ann.expect("WELCOME 1 3 1");
+        ann.expect("START");
+        let mut bob = Client::connect(addr);
+        bob.send("HELLO bob");
+        bob.expect("WELCOME 1 3 1");
+        ann.expect("JOINED bob");
+        bob.expect("START");
+
+        ann.send(&format!("GUESS {wrong}"));
+        ann.expect("OUT");
+        ann.send(&format!("GUESS {secret}"));
+        ann.expect("OUT");
+        // Leaving counts as giving up.
+        bob.send("QUIT");
+        ann.expect("LEFT bob");
+        ann.expect(&format!("OVER {secret}"));
+        assert_eq!(server.join().unwrap().0, Ending::NobodyWon { secret });
+    }
+
+    #[test]
+    fn parses_serve_and_join_options() {
+        let args = |args: &[&str]| -> Vec<String> { args.iter().map(|s| s.to_string()).collect() };
+        let (options, addr, players) = parse_serve_args(&args(&[
+            "serve",
+            "-d",
+            "easy",
+            "-p",
+            "3",
+            "--listen=0.0.0.0:9000",
+        ]))
+        .unwrap();
+        assert_eq!(
+            options.settings,
+            crate::ch2::game::Difficulty::Easy.settings()
+        );
+        assert_eq!((addr.as_str(), players), ("0.0.0.0:9000", 3));
+        assert_eq!(
+            parse_serve_args(&args(&["serve", "--players", "0"]))
+                .unwrap_err()
+                .to_string(),
+            "invalid value `0` for --players; expected a number of at least 1"
+        );
+        assert!(parse_serve_args(&args(&["serve", "--scores"])).is_err());
+
+        let (addr, name) = parse_join_args(&args(&["join", "-n", "ann"])).unwrap();
+        assert_eq!((addr.as_str(), name.as_str()), (DEFAULT_ADDR, "ann"));
+        assert_eq!(
+            parse_join_args(&args(&["join", "extra"])),
+            Err(ArgsError::UnexpectedArgument(String::from("extra")))
+        );
+    }
+
+    #[test]
+    fn joining_plays_over_the_connection() {
+        let settings = Settings::default();
+        let secret = GuessingGame::from_seed(settings, 5).secret();
+        let (addr, server) = serve(settings, 1);
+
+        let input = format!("\n{}\n{secret}\n", secret + 1);
+        let mut out = Vec::new();
+        assert!(join(addr, "ann", Box::new(io::Cursor::new(input)), &mut out).unwrap());
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "Joined as ann. Guess the number between 1 and 100!\n\
+                 The race is on! Please input your guess.\n\
+                 {} is too big!\n\
+                 You win, in 2 guesses!\n",
+                secret + 1
+            )
+        );
+        assert!(matches!(server.join().unwrap().0, Ending::Won { .. }));
+    }
+}
@@ -0,0 +1,435 @@
+// High scores for `hello_cargo guess`, kept across runs.
+//
+// Every won game on one of the preset difficulties is a score: who played,
+// at which difficulty, in how many guesses and how long, and when. Fewer
+// guesses rank higher, then less time. The scores live in a tab-separated
+// file,
+//
+//   # hello_cargo guess scores 1
+//   1792194639	normal	5	12345	alice       (time, difficulty, guesses, ms, player)
+//
+// at `$GUESS_SCORES`, or `$XDG_DATA_HOME/hello_cargo/scores.tsv`, or
+// `~/.local/share/hello_cargo/scores.tsv`.
+//
+// The file is rewritten as a whole through a temporary file that is renamed
+// over it, so a crash never leaves it half-written. If it is damaged anyway,
+// the lines that can be read are kept, and a copy of the damaged file is
+// saved next to it as `scores.tsv.corrupt` before it is next rewritten, or as
+// `scores.tsv.corrupt.1`, `.2` and so on if earlier copies are still there.
+// Recording a score holds a lock on `scores.tsv.lock` from reading the file
+// to rewriting it, so games that end at the same moment both get recorded.
+
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use super::game::Difficulty;
+use crate::fsutil::{LockFile, TempFile};
+
+const HEADER: &str = "# hello_cargo guess scores 1";
+
+/// How many scores each leaderboard shows.
+pub const LEADERBOARD_SIZE: usize = 10;
+
+/// One won game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Score {
+    pub player: String,
+    pub difficulty: Difficulty,
+    pub attempts: u32,
+    pub duration: Duration,
+    /// When the game was won, in seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+impl Score {
+    // Better scores sort first.
+    fn rank_key(&self) -> (u32, Duration, u64) {
+        (self.attempts, self.duration, self.timestamp)
+    }
+}
+
+/// All the scores recorded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ScoreBoard {
+    pub scores: Vec<Score>,
+}
+
+/// A score file after loading, and what was wrong with it, if anything.
+#[derive(Debug)]
+pub struct Loaded {
+    pub board: ScoreBoard,
+    /// How many lines couldn't be read.
+    pub skipped: usize,
+    /// Where the damaged file was copied before it was rewritten, if it was.
+    pub backup: Option<PathBuf>,
+}
+
+/// The score file to use, if a place for it can be found.
+pub fn scores_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("GUESS_SCORES").filter(|path| !path.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let data_dir = env::var_os("XDG_DATA_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".local/share")))?;
+    Some(data_dir.join("hello_cargo").join("scores.tsv"))
+}
+
+impl ScoreBoard {
+    /// Parses a score file, skipping the lines that can't be read; returns
+    /// how many were skipped, counting a missing header as one.
+    pub fn parse(text: &str) -> (ScoreBoard, usize) {
+        let mut lines = text.lines().peekable();
+        let mut skipped = 0;
+        if lines.next_if_eq(&HEADER).is_none() && !text.is_empty() {
+            skipped += 1;
+        }
+
+        let mut board = ScoreBoard::default();
+        for line in lines {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_score(line) {
+                Some(score) => board.scores.push(score),
+                None => skipped += 1,
+            }
+        }
+        (board, skipped)
+    }
+
+    /// Reads the score file at `path`, without changing anything; a missing
+    /// one is empty.
+    pub fn load(path: &Path) -> io::Result<Loaded> {
+        let (board, skipped) = ScoreBoard::parse(&String::from_utf8_lossy(&read(path)?));
+        Ok(Loaded {
+            board,
+            skipped,
+            backup: None,
+        })
+    }
+
+    /// Adds `score` to the score file at `path` and returns the scores saved.
+    ///
+    /// The file is locked from reading it to rewriting it. If it's damaged,
+    /// it is copied to `<path>.corrupt` first, or to `<path>.corrupt.<n>` if
+    /// that exists, so nothing is lost for good when the readable scores are
+    /// saved over it.
+    pub fn record(path: &Path, score: Score) -> io::Result<Loaded> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let _lock = LockFile::acquire(path)?;
+        let bytes = read(path)?;
+        let (mut board, skipped) = ScoreBoard::parse(&String::from_utf8_lossy(&bytes));
+        let backup = if skipped > 0 {
+            Some(back_up(path, &bytes)?)
+        } else {
+            None
+        };
+        board.scores.push(score);
+        board.save(path)?;
+        Ok(Loaded {
+            board,
+            skipped,
+            backup,
+        })
+    }
+
+    /// Writes every score to `path` in one step, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            fs::create_dir_all(dir)?;
+        }
+        let mut temp = TempFile::next_to(path)?;
+        writeln!(temp.writer, "{HEADER}")?;
+        for score in &self.scores {
+            // Tabs and line breaks would split the record.
+            let player: String = score
+                .player
+                .chars()
+                .map(|c| if c.is_control() { ' ' } else { c })
+                .collect();
+            writeln!(
+                temp.writer,
+                "{}\t{}\t{}\t{}\t{player}",
+                score.timestamp,
+                score.difficulty.name(),
+                score.attempts,
+                score.duration.as_millis()
+            )?;
+        }
+        temp.replace(path)
+    }
+
+    /// The best scores at `difficulty`, best first.
+    pub fn leaderboard(&self, difficulty: Difficulty) -> Vec<&Score> {
+        let mut scores: Vec<&Score> = self
+            .scores
+            .iter()
+            .filter(|score| score.difficulty == difficulty)
+            .collect();
+        scores.sort_by_key(|score| score.rank_key());
+        scores.truncate(LEADERBOARD_SIZE);
+        scores
+    }
+
+    /// `player`'s best score at `difficulty`, if they have one.
+    pub fn personal_best(&self, player: &str, difficulty: Difficulty) -> Option<&Score> {
+        self.scores
+            .iter()
+            .filter(|score| score.player == player && score.difficulty == difficulty)
+            .min_by_key(|score| score.rank_key())
+    }
+
+    /// Writes the leaderboard of every difficulty, then `player`'s bests.
+    pub fn write_table(&self, player: &str, out: &mut dyn Write) -> io::Result<()> {
+        if self.scores.is_empty() {
+            return writeln!(out, "No scores yet.");
+        }
+        let width = self
+            .scores
+            .iter()
+            .map(|score| score.player.chars().count())
+            .max()
+            .unwrap_or(0);
+        for difficulty in Difficulty::ALL {
+            let leaderboard = self.leaderboard(difficulty);
+            if leaderboard.is_empty() {
+                continue;
+            }
+            writeln!(out, "{}:", difficulty.name())?;
+            for (rank, score) in leaderboard.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{:>4}. {:<width$}  {}",
+                    rank + 1,
+                    score.player,
+                    describe(score)
+                )?;
+            }
+        }
+
+        writeln!(out, "Personal bests for {player}:")?;
+        for difficulty in Difficulty::ALL {
+            let best = self
+                .personal_best(player, difficulty)
+                .map_or(String::from("-"), describe);
+            writeln!(out, "  {:<6}  {best}", difficulty.name())?;
+        }
+        Ok(())
+    }
+}
+
+// `5 guesses  12.3s  2026-10-16`
+fn describe(score: &Score) -> String {
+    format!(
+        "{:>2} {:<7}  {:>6.1}s  {}",
+        score.attempts,
+        if score.attempts == 1 {
+            "guess"
+        } else {
+            "guesses"
+        },
+        score.duration.as_secs_f64(),
+        date(score.timestamp)
+    )
+}
+
+// Writes `bytes` to the first of `<path>.corrupt`, `<path>.corrupt.1`, ...
+// that doesn't exist yet, and returns its path.
+fn back_up(path: &Path, bytes: &[u8]) -> io::Result<PathBuf> {
+    for n in 0.. {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".corrupt");
+        if n > 0 {
+            name.push(format!(".{n}"));
+        }
+        let backup = PathBuf::from(name);
+        match File::options().write(true).create_new(true).open(&backup) {
+            Ok(mut file) => {
+                file.write_all(bytes)?;
+                return Ok(backup);
+            }
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!("ran out of backup names")
+}
+
+// The bytes of the file at `path`, with a missing file empty.
+fn read(path: &Path) -> io::Result<Vec<u8>> {
+    match fs::read(path) {
+        Ok(bytes) => Ok(bytes),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+fn parse_score(line: &str) -> Option<Score> {
+    let mut fields = line.splitn(5, '\t');
+    let timestamp = fields.next()?.parse().ok()?;
+    let difficulty = Difficulty::parse(fields.next()?)?;
+    let attempts = fields.next()?.parse().ok().filter(|&n| n > 0)?;
+    let duration = Duration::from_millis(fields.next()?.parse().ok()?);
+    let player = fields.next().filter(|player| !player.is_empty())?;
+    Some(Score {
+        player: player.to_string(),
+        difficulty,
+        attempts,
+        duration,
+        timestamp,
+    })
+}
+
+/// The UTC date of `timestamp`, seconds since the Unix epoch, as `YYYY-MM-DD`.
+pub fn date(timestamp: u64) -> String {
+    // Howard Hinnant's days-to-civil algorithm, for years from 1970 on.
+    let days = timestamp / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process;
+
+    fn score(player: &str, difficulty: Difficulty, attempts: u32, secs: u64) -> Score {
+        Score {
+            player: player.to_string(),
+            difficulty,
+            attempts,
+            duration: Duration::from_secs(secs),
+            timestamp: 1_792_108_800,
+        }
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(date(0), "1970-01-01");
+        assert_eq!(date(951_782_400), "2000-02-29");
+        assert_eq!(date(1_792_108_800 + 86_399), "2026-10-16");
+    }
+
+    #[test]
+    fn saves_and_loads() {
+        let path = env::temp_dir().join(format!("guess-scores-{}/scores.tsv", process::id()));
+        let board = ScoreBoard {
+            scores: vec![
+                score("alice", Difficulty::Normal, 5, 12),
+                score("bob\tby", Difficulty::Hard, 9, 80),
+            ],
+        };
+        board.save(&path).unwrap();
+        let loaded = ScoreBoard::load(&path).unwrap();
+        assert_eq!((loaded.skipped, loaded.backup), (0, None));
+        assert_eq!(loaded.board.scores[0], board.scores[0]);
+        assert_eq!(loaded.board.scores[1].player, "bob by");
+        fs::remove_dir_all(path.parent().unwrap()).unwrap();
+    }
+
+    #[test]
+    fn damaged_files_keep_what_can_be_read() {
+        let dir = env::temp_dir().join(format!("guess-scores-corrupt-{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("scores.tsv");
+        let damaged = format!(
+            "{HEADER}\n1792108800\tnormal\t5\t12000\talice\n17921\tnor\x00\n\
+             1792108800\textreme\t1\t1\tmallory\n1792108800\teasy\t3\t2500\tbob"
+        );
+        fs::write(&path, &damaged).unwrap();
+
+        // Only reading leaves the file as it is.
+        let loaded = ScoreBoard::load(&path).unwrap();
+        assert_eq!((loaded.skipped, loaded.backup), (2, None));
+        assert_eq!(loaded.board.scores.len(), 2);
+        assert!(!dir.join("scores.tsv.corrupt").exists());
+
+        let recorded = ScoreBoard::record(&path, score("bea", Difficulty::Hard, 7, 30)).unwrap();
+        assert_eq!(recorded.skipped, 2);
+        assert_eq!(recorded.board.scores.len(), 3);
+        assert_eq!(recorded.backup, Some(dir.join("scores.tsv.corrupt")));
+        assert_eq!(
+            fs::read_to_string(dir.join("scores.tsv.corrupt")).unwrap(),
+            damaged
+        );
+        assert_eq!(ScoreBoard::load(&path).unwrap().skipped, 0);
+
+        fs::write(&path, "not scores at all").unwrap();
+        let loaded = ScoreBoard::load(&path).unwrap();
+        assert_eq!((loaded.skipped, loaded.board.scores.len()), (2, 0));
+
+        // A second damaged file gets a backup of its own.
+        let recorded = ScoreBoard::record(&path, score("bea", Difficulty::Hard, 6, 30)).unwrap();
+        assert_eq!(recorded.backup, Some(dir.join("scores.tsv.corrupt.1")));
+        assert_eq!(
+            fs::read_to_string(dir.join("scores.tsv.corrupt")).unwrap(),
+            damaged
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("scores.tsv.corrupt.1")).unwrap(),
+            "not scores at all"
+        );
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn leaderboards_and_personal_bests() {
+        let board = ScoreBoard {
+            scores: vec![
+                score("alice", Difficulty::Normal, 6, 10),
+                score("bob", Difficulty::Normal, 5, 40),
+                score("alice", Difficulty::Normal, 5, 20),
+                score("bob", Difficulty::Easy, 1, 3),
+            ],
+        };
+        let normal: Vec<(&str, u32)> = board
+            .leaderboard(Difficulty::Normal)
+            .iter()
+            .map(|score| (score.player.as_str(), score.attempts))
+            .collect();
+        assert_eq!(normal, [("alice", 5), ("bob", 5), ("alice", 6)]);
+        assert_eq!(
+            board
+                .personal_best("alice", Difficulty::Normal)
+                .unwrap()
+                .duration,
+            Duration::from_secs(20)
+        );
+        assert_eq!(board.personal_best("alice", Difficulty::Easy), None);
+
+        let mut out = Vec::new();
+        board.write_table("alice", &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "easy:\n\
+             \x20  1. bob     1 guess       3.0s  2026-10-16\n\
+             normal:\n\
+             \x20  1. alice   5 guesses    20.0s  2026-10-16\n\
+             \x20  2. bob     5 guesses    40.0s  2026-10-16\n\
+             \x20  3. alice   6 guesses    10.0s  2026-10-16\n\
+             Personal bests for alice:\n\
+             \x20 easy    -\n\
+             \x20 normal   5 guesses    20.0s  2026-10-16\n\
+             \x20 hard    -\n"
+        );
+    }
+}
@@ -0,0 +1,263 @@
+// The best way to play the guessing game: always guess halfway between the
+// smallest and largest numbers the answers so far still allow. Every answer
+// then rules out half of what's left, so a range of n numbers takes at most
+// as many guesses as n has binary digits, 7 for 1 to 100, and no strategy
+// can promise fewer.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+
+use super::game::{GuessingGame, Outcome, Settings};
+
+/// The most guesses halving the range can take for `min..=max`: the
+/// number of binary digits in its size, and 0 if it's empty.
+pub fn worst_case_attempts(min: i32, max: i32) -> u32 {
+    if min > max {
+        return 0;
+    }
+    let size = (i64::from(max) - i64::from(min) + 1) as u64;
+    u64::BITS - size.leading_zeros()
+}
+
+/// Keeps track of the numbers the answers so far allow, and picks the next guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Solver {
+    low: i32,
+    high: i32,
+}
+
+impl Solver {
+    /// A solver for a game with `settings`, before any guess.
+    pub fn new(settings: Settings) -> Solver {
+        Solver {
+            low: settings.min,
+            high: settings.max,
+        }
+    }
+
+    /// The smallest and largest numbers the secret can still be, if the
+    /// answers so far leave any.
+    pub fn remaining(&self) -> Option<(i32, i32)> {
+        (self.low <= self.high).then_some((self.low, self.high))
+    }
+
+    /// The guess that rules out the most numbers whatever the answer: the
+    /// middle of what remains, rounding down.
+    pub fn next_guess(&self) -> Option<i32> {
+        let (low, high) = self.remaining()?;
+        // In i64 so the difference can't overflow; it's never negative, so
+        // dividing it rounds down even when the range is below zero.
+        Some((i64::from(low) + (i64::from(high) - i64::from(low)) / 2) as i32)
+    }
+
+    /// Narrows down the secret with the answer to `guess`; answers that say
+    /// nothing about it, like an out-of-range guess, are ignored.
+    pub fn update(&mut self, guess: i32, outcome: Outcome) {
+        match outcome {
+            Outcome::TooLow => self.low = self.low.max(guess.saturating_add(1)),
+            Outcome::TooHigh => self.high = self.high.min(guess.saturating_sub(1)),
+            Outcome::Won { .. } => (self.low, self.high) = (guess, guess),
+            Outcome::Lost { secret } => (self.low, self.high) = (secret, secret),
+            Outcome::OutOfRange { .. } => {}
+        }
+    }
+}
+
+/// Plays `game` to the end with `Solver`, calling `each` with every guess
+/// and its answer; returns the final outcome.
+pub fn play_optimally(game: &mut GuessingGame, mut each: impl FnMut(i32, Outcome)) -> Outcome {
+    let mut solver = Solver::new(game.settings());
+    loop {
+        // The game answers truthfully, so the secret always remains.
+        let guess = solver.next_guess().expect("the secret was ruled out");
+        let outcome = game.guess(guess);
+        each(guess, outcome);
+        if game.is_over() {
+            return outcome;
+        }
+        solver.update(guess, outcome);
+    }
+}
+
+/// How `play_optimally` did over many games.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Benchmark {
+    /// How many games were won in each number of guesses.
+    pub wins: BTreeMap<u32, u32>,
+    /// How many games ran out of guesses.
+    pub losses: u32,
+}
+
+impl Benchmark {
+    /// Plays `games` games with `settings`, seeded with `first_seed` and the
+    /// numbers after it.
+    pub fn run(settings: Settings, games: u32, first_seed: u64) -> Benchmark {
+        let mut benchmark = Benchmark::default();
+        for seed in (first_seed..).take(games as usize) {
+            let mut game = GuessingGame::from_seed(settings, seed);
+            match play_optimally(&mut game, |_, _| {}) {
+                Outcome::Won { attempts } => *benchmark.wins.entry(attempts).or_default() += 1,
+                _ => benchmark.losses += 1,
+            }
+        }
+        benchmark
+    }
+
+    pub fn games(&self) -> u32 {
+        self.wins.values().sum::<u32>() + self.losses
+    }
+
+    /// The average number of guesses per won game.
+    pub fn mean_attempts(&self) -> Option<f64> {
+        let won: u32 = self.wins.values().sum();
+        let guesses: u64 = self
+            .wins
+            .iter()
+            .map(|(&attempts, &count)| u64::from(attempts) * u64::from(count))
+            .sum();
+        (won > 0).then(|| guesses as f64 / f64::from(won))
+    }
+
+    /// Writes a row for each number of guesses and one for losses, with
+    /// the share of games and a bar, then the average.
+    pub fn write_distribution(&self, out: &mut dyn Write) -> io::Result<()> {
+        const BAR_WIDTH: u64 = 40;
+        let games = self.games().max(1);
+        let most = self
+            .wins
+            .values()
+            .copied()
+            .chain([self.losses])
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        let rows = self
+            .wins
+            .iter()
+            .map(|(attempts, &count)| (attempts.to_string(), count))
+            .chain([(String::from("lost"), self.losses)]);
+
+        writeln!(out, "guesses  games")?;
+        for (label, count) in rows {
+            let share = f64::from(count) * 100.0 / f64::from(games);
+            let bar = (u64::from(count) * BAR_WIDTH).div_ceil(u64::from(most)) as usize;
+            let row = format!("{label:>7}  {count:>5}  {share:>5.1}%  {}", "#".repeat(bar));
+            writeln!(out, "{}", row.trim_end())?;
+        }
+        match self.mean_attempts() {
+            Some(mean) => writeln!(out, "Average: {mean:.2} guesses per win."),
+            None => writeln!(out, "No games were won."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ch2::game::Difficulty;
+
+    #[test]
+    fn worst_cases() {
+        assert_eq!(worst_case_attempts(1, 1), 1);
+        assert_eq!(worst_case_attempts(1, 3), 2);
+        assert_eq!(worst_case_attempts(1, 4), 3);
+        assert_eq!(worst_case_attempts(1, 100), 7);
+        assert_eq!(worst_case_attempts(1, 1000), 10);
+        assert_eq!(worst_case_attempts(i32::MIN, i32::MAX), 33);
+        assert_eq!(worst_case_attempts(2, 1), 0);
+    }
+
+    #[test]
+    fn solver_narrows_the_range() {
+        let mut solver = Solver::new(Settings::default());
+        assert_eq!(solver.next_guess(), Some(50));
+        solver.update(50, Outcome::TooHigh);
+        assert_eq!(solver.next_guess(), Some(25));
+        solver.update(25, Outcome::TooLow);
+        assert_eq!(solver.remaining(), Some((26, 49)));
+        // A worse guess than the solver's still teaches it what it can.
+        solver.update(10, Outcome::TooLow);
+        solver.update(0, Outcome::OutOfRange { min: 1, max: 100 });
+        assert_eq!(solver.remaining(), Some((26, 49)));
+        solver.update(30, Outcome::TooLow);
+        solver.update(31, Outcome::TooHigh);
+        assert_eq!(solver.next_guess(), None);
+
+        let below_zero = |min, max| {
+            Solver::new(Settings {
+                min,
+                max,
+                max_attempts: None,
+            })
+            .next_guess()
+        };
+        assert_eq!(below_zero(-10, -1), Some(-6));
+        assert_eq!(below_zero(-1, 0), Some(-1));
+        assert_eq!(below_zero(i32::MIN, i32::MAX), Some(-1));
+    }
+
+    #[test]
+    fn every_secret_is_found_within_the_worst_case() {
+        for (min, max) in [(1, 1), (1, 2), (1, 7), (1, 100), (-100, -1), (-50, 49)] {
+            let settings = Settings {
+                min,
+                max,
+                max_attempts: None,
+            };
+            let worst = worst_case_attempts(min, max);
+            for seed in 0..200 {
+                let mut game = GuessingGame::from_seed(settings, seed);
+                let mut guesses = Vec::new();
+                let outcome = play_optimally(&mut game, |guess, _| guesses.push(guess));
+                assert_eq!(guesses.last(), Some(&game.secret()));
+                assert!(matches!(outcome, Outcome::Won { attempts } if attempts <= worst));
+            }
+        }
+    }
+
+    #[test]
+    fn benchmarks() {
+        let benchmark = Benchmark::run(Difficulty::Normal.settings(), 300, 0);
+        assert_eq!(benchmark.games(), 300);
+        assert_eq!(benchmark.losses, 0);
+        assert!(benchmark.wins.keys().all(|&attempts| attempts <= 7));
+        assert_eq!(
+            Benchmark::run(Difficulty::Normal.settings(), 300, 0),
+            benchmark
+        );
+
+        let short = Settings {
+            max_attempts: Some(1),
+            ..Settings::default()
+        };
+        let benchmark = Benchmark::run(short, 50, 0);
+        assert_eq!(
+            benchmark.wins.get(&1).copied().unwrap_or(0) + benchmark.losses,
+            50
+        );
+        assert!(benchmark.losses > 0);
+        assert_eq!(Benchmark::default().mean_attempts(), None);
+    }
+
+    #[test]
+    fn distributions() {
+        let benchmark = Benchmark {
+            wins: BTreeMap::from([(1, 1), (2, 3)]),
+            losses: 0,
+        };
+        let mut out = Vec::new();
+        benchmark.write_distribution(&mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            format!(
+                "guesses  games\n      \
+                 1      1   25.0%  {}\n      \
+                 2      3   75.0%  {}\n   \
+                 lost      0    0.0%\n\
+                 Average: 1.75 guesses per win.\n",
+                "#".repeat(14),
+                "#".repeat(40)
+            )
+        );
+    }
+}
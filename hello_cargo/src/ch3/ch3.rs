@@ -86,50 +86,55 @@
 // Ownership (a unique Rust concept)
 
 
+use std::io::{self, Write};
+
 // --- Variable Mutability and Shadowing ---
-fn variable_examples() {
+fn variable_examples(out: &mut dyn Write) -> io::Result<()> {
     // Immutable by default
     let x = 5;
-    println!("Immutable x: {x}");
+    writeln!(out, "Immutable x: {x}")?;
 
     // Mutable variable
     let mut y = 10;
-    println!("Mutable y before: {y}");
+    writeln!(out, "Mutable y before: {y}")?;
     y = 15;
-    println!("Mutable y after: {y}");
+    writeln!(out, "Mutable y after: {y}")?;
 
     // Constant
     const SECONDS_IN_AN_HOUR: u32 = 60 * 60;
-    println!("Seconds in an hour: {SECONDS_IN_AN_HOUR}");
+    writeln!(out, "Seconds in an hour: {SECONDS_IN_AN_HOUR}")?;
 
     // Shadowing (can change type)
     let z = "hello";
     let z = z.len(); // z is now usize, not &str
-    println!("Shadowed z (length): {z}");
+    writeln!(out, "Shadowed z (length): {z}")?;
+    Ok(())
 }
 
 // --- Data Types: Scalars and Compound Types ---
-fn scalar_and_compound_types() {
+#[allow(clippy::approx_constant)] // 3.1415 is the book's example float, not an approximation of PI
+fn scalar_and_compound_types(out: &mut dyn Write) -> io::Result<()> {
     // Scalar types
     let int: i32 = -42;
     let float = 3.1415; // f64 by default
     let is_true: bool = true;
     let letter: char = 'R';
 
-    println!("int = {int}, float = {float}, is_true = {is_true}, letter = {letter}");
+    writeln!(out, "int = {int}, float = {float}, is_true = {is_true}, letter = {letter}")?;
 
     // Tuple
     let tup: (i32, f64, u8) = (500, 6.4, 1);
     let (a, b, c) = tup;
-    println!("Tuple destructure: a={a}, b={b}, c={c}");
-    println!("Access tuple directly: tup.0 = {}, tup.1 = {}", tup.0, tup.1);
+    writeln!(out, "Tuple destructure: a={a}, b={b}, c={c}")?;
+    writeln!(out, "Access tuple directly: tup.0 = {}, tup.1 = {}", tup.0, tup.1)?;
 
     // Array
     let arr = [1, 2, 3, 4, 5];
-    println!("Array first element: {}", arr[0]);
+    writeln!(out, "Array first element: {}", arr[0])?;
 
     let same_value_arr = [3; 4]; // [3, 3, 3, 3]
-    println!("Array with same values: {:?}", same_value_arr);
+    writeln!(out, "Array with same values: {:?}", same_value_arr)?;
+    Ok(())
 }
 
 // --- Functions and Return Values ---
@@ -138,38 +143,39 @@ fn add(x: i32, y: i32) -> i32 {
     x + y
 }
 
-fn function_examples() {
-    fn print_measurement(value: i32, unit: char) {
-        println!("Measurement: {value}{unit}");
+fn function_examples(out: &mut dyn Write) -> io::Result<()> {
+    fn print_measurement(out: &mut dyn Write, value: i32, unit: char) -> io::Result<()> {
+        writeln!(out, "Measurement: {value}{unit}")
     }
-    print_measurement(42, 'm');
+    print_measurement(out, 42, 'm')?;
 
     let sum = add(7, 8);
-    println!("Sum from add(): {sum}");
+    writeln!(out, "Sum from add(): {sum}")?;
 
     // Expression block
     let expr_result = {
         let temp = 2;
         temp * temp + 1
     };
-    println!("Result from expression block: {expr_result}");
+    writeln!(out, "Result from expression block: {expr_result}")?;
+    Ok(())
 }
 
 // --- Control Flow: if, loops, while, for ---
-fn control_flow_examples() {
+fn control_flow_examples(out: &mut dyn Write) -> io::Result<()> {
     // if-else
     let n = 7;
     if n < 5 {
-        println!("{n} is less than 5");
+        writeln!(out, "{n} is less than 5")?;
     } else if n == 5 {
-        println!("{n} is exactly 5");
+        writeln!(out, "{n} is exactly 5")?;
     } else {
-        println!("{n} is greater than 5");
+        writeln!(out, "{n} is greater than 5")?;
     }
 
     // if as expression
     let parity = if n % 2 == 0 { "even" } else { "odd" };
-    println!("{n} is {parity}");
+    writeln!(out, "{n} is {parity}")?;
 
     // loop with break and value
     let mut count = 0;
@@ -179,42 +185,44 @@ fn control_flow_examples() {
             break count * 2;
         }
     };
-    println!("Loop returned: {double}");
+    writeln!(out, "Loop returned: {double}")?;
 
     // while loop
     let mut countdown = 3;
     while countdown > 0 {
-        println!("{countdown}...");
+        writeln!(out, "{countdown}...")?;
         countdown -= 1;
     }
-    println!("LIFTOFF!");
+    writeln!(out, "LIFTOFF!")?;
 
     // for loop over array
     let nums = [10, 20, 30, 40];
     for val in nums {
-        println!("Array element: {val}");
+        writeln!(out, "Array element: {val}")?;
     }
 
     // for loop with range and rev
     for i in (1..=3).rev() {
-        println!("{i}!");
+        writeln!(out, "{i}!")?;
     }
-    println!("Go!");
+    writeln!(out, "Go!")?;
+    Ok(())
 }
 
 // The following function demonstrates all of the above examples.
 // Remove or comment out the following when integrating parts above into a real project.
-pub fn ch3() {
-    println!("--- Variable Examples ---");
-    variable_examples();
+pub fn ch3(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "--- Variable Examples ---")?;
+    variable_examples(out)?;
 
-    println!("\n--- Scalar and Compound Types ---");
-    scalar_and_compound_types();
+    writeln!(out, "\n--- Scalar and Compound Types ---")?;
+    scalar_and_compound_types(out)?;
 
-    println!("\n--- Function Examples ---");
-    function_examples();
+    writeln!(out, "\n--- Function Examples ---")?;
+    function_examples(out)?;
 
-    println!("\n--- Control Flow Examples ---");
-    control_flow_examples();
+    writeln!(out, "\n--- Control Flow Examples ---")?;
+    control_flow_examples(out)?;
+    Ok(())
 }
 
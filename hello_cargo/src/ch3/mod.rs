@@ -1 +1,37 @@
-pub mod ch3;
\ No newline at end of file
+#![allow(clippy::module_inception)]
+
+pub mod ch3;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch3;
+
+impl Chapter for Ch3 {
+    fn id(&self) -> &'static str {
+        "ch3"
+    }
+
+    fn title(&self) -> &'static str {
+        "Common Programming Concepts"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "variables",
+            "mutability",
+            "shadowing",
+            "data types",
+            "functions",
+            "control flow",
+            "loops",
+        ]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch3::ch3(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch3];
@@ -92,52 +92,55 @@
 // Summary: Ownership lets Rust manage memory automatically and safely, but requires understanding move semantics. Heap data moves by default; copying is explicit; stack data is trivially Copy. Functions move or copy ownership by default. References (next) allow borrowing without moving ownership.
 
 
+use std::io::{self, Write};
+
 // ---- Example code for Chapter 4.1 Ownership ----
-pub fn ch4_1_demo() {
+pub fn ch4_1_demo(out: &mut dyn Write) -> io::Result<()> {
     // Stack data: Copy
     let x = 42;
     let y = x;
-    println!("x = {x}, y = {y}"); // Both valid
+    writeln!(out, "x = {x}, y = {y}")?; // Both valid
 
     // Heap data: Move
     let s1 = String::from("hello");
     let s2 = s1; // s1 moved to s2
     // println!("{s1}"); // Uncommenting this line would cause a compile error!
-    println!("s2 = {s2}");
+    writeln!(out, "s2 = {s2}")?;
 
     // Clone for deep copy
     let s3 = String::from("world");
     let s4 = s3.clone();
-    println!("s3 = {s3}, s4 = {s4}");
+    writeln!(out, "s3 = {s3}, s4 = {s4}")?;
 
     // Ownership and functions
     let s5 = String::from("Rust");
-    takes_ownership(s5); // s5 is moved here and dropped at end of function
+    takes_ownership(out, s5)?; // s5 is moved here and dropped at end of function
     // println!("{s5}"); // Compile error: s5 was moved
 
     let n = 100;
-    makes_copy(n); // n implements Copy, so still valid
-    println!("n = {n}");
+    makes_copy(out, n)?; // n implements Copy, so still valid
+    writeln!(out, "n = {n}")?;
 
     // Return ownership
     let s6 = gives_ownership();
-    println!("s6 = {s6}");
+    writeln!(out, "s6 = {s6}")?;
     let s7 = String::from("ownership");
     let s8 = takes_and_gives_back(s7);
-    println!("s8 = {s8}");
+    writeln!(out, "s8 = {s8}")?;
 
     // Tuple trick for getting value and its property without references
     let s9 = String::from("tuple");
     let (s10, len) = calculate_length(s9);
-    println!("The length of '{s10}' is {len}.");
+    writeln!(out, "The length of '{s10}' is {len}.")?;
+    Ok(())
 }
 
-fn takes_ownership(some_string: String) {
-    println!("Took ownership of: {some_string}");
+fn takes_ownership(out: &mut dyn Write, some_string: String) -> io::Result<()> {
+    writeln!(out, "Took ownership of: {some_string}")
 }
 
-fn makes_copy(some_int: i32) {
-    println!("Copied int: {some_int}");
+fn makes_copy(out: &mut dyn Write, some_int: i32) -> io::Result<()> {
+    writeln!(out, "Copied int: {some_int}")
 }
 
 fn gives_ownership() -> String {
@@ -92,24 +92,26 @@
 // Next: Slices (next section in Rust book)
 
 
+use std::io::{self, Write};
+
 // ---- Example code for Chapter 4.2 References and Borrowing ----
 
-pub fn ch4_2_demo() {
+pub fn ch4_2_demo(out: &mut dyn Write) -> io::Result<()> {
     // Immutable reference (borrowing)
     let s1 = String::from("hello");
     let len = calculate_length(&s1);
-    println!("The length of '{s1}' is {len}.");
+    writeln!(out, "The length of '{s1}' is {len}.")?;
 
     // Mutable reference
     let mut s2 = String::from("hi");
     change(&mut s2);
-    println!("s2 after mutation: {s2}");
+    writeln!(out, "s2 after mutation: {s2}")?;
 
     // Multiple immutable references: OK
     let s3 = String::from("immut");
     let r1 = &s3;
     let r2 = &s3;
-    println!("r1 = {r1}, r2 = {r2}");
+    writeln!(out, "r1 = {r1}, r2 = {r2}")?;
 
     // Multiple mutable references: NOT OK
     // let mut s4 = String::from("nope");
@@ -127,12 +129,15 @@ pub fn ch4_2_demo() {
     // Non-overlapping references: OK
     let mut s6 = String::from("scopes");
     let i1 = &s6;
-    println!("i1 = {i1}");
+    writeln!(out, "i1 = {i1}")?;
     let m1 = &mut s6;
+    #[allow(clippy::single_char_add_str)] // the book pushes a &str here
     m1.push_str("!");
-    println!("m1 = {m1}");
+    writeln!(out, "m1 = {m1}")?;
+    Ok(())
 }
 
+#[allow(clippy::ptr_arg)] // &str comes in the next section, on slices
 fn calculate_length(s: &String) -> usize {
     s.len()
 }
@@ -1,3 +1,69 @@
 pub mod ch4_1;
 pub mod ch4_2;
-pub mod ch4_3;
\ No newline at end of file
+pub mod ch4_3;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch4_1;
+
+impl Chapter for Ch4_1 {
+    fn id(&self) -> &'static str {
+        "ch4_1"
+    }
+
+    fn title(&self) -> &'static str {
+        "What Is Ownership?"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["ownership", "move", "clone", "Copy", "stack", "heap"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch4_1::ch4_1_demo(out)
+    }
+}
+
+pub struct Ch4_2;
+
+impl Chapter for Ch4_2 {
+    fn id(&self) -> &'static str {
+        "ch4_2"
+    }
+
+    fn title(&self) -> &'static str {
+        "References and Borrowing"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["references", "borrowing", "mutable references", "dangling references"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch4_2::ch4_2_demo(out)
+    }
+}
+
+pub struct Ch4_3;
+
+impl Chapter for Ch4_3 {
+    fn id(&self) -> &'static str {
+        "ch4_3"
+    }
+
+    fn title(&self) -> &'static str {
+        "The Slice Type"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["slices", "string slices", "first_word"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch4_3::ch4_3_demo(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch4_1, &Ch4_2, &Ch4_3];
@@ -3,7 +3,10 @@
 // Structs are similar to tuples but with named fields.
 // This makes them more flexible and readable than tuples.
 
+use std::io::{self, Write};
+
 /// Define a struct with named fields.
+#[allow(dead_code)] // only `email` is read back
 struct User {
     active: bool,
     username: String,  // owned types preferred
@@ -11,7 +14,7 @@ struct User {
     sign_in_count: u64,
 }
 
-pub fn ch5_1() {
+pub fn ch5_1(out: &mut dyn Write) -> io::Result<()> {
     // Create a new instance of User
     let mut user1 = User {
         active: true,
@@ -21,7 +24,7 @@ pub fn ch5_1() {
     };
 
     // Accessing a field using dot notation
-    println!("Email: {}", user1.email);
+    writeln!(out, "Email: {}", user1.email)?;
 
     // Modifying a field (entire struct must be mutable)
     user1.email = String::from("anotheremail@example.com");
@@ -49,6 +52,7 @@ pub fn ch5_1() {
     // ======================================================
 
     // Using ..user1 moves values, so user1.username is no longer valid after this
+    #[allow(unused_variables)]
     let user3 = User {
         email: String::from("user3@example.com"),
         ..user2
@@ -60,9 +64,11 @@ pub fn ch5_1() {
     // Tuple Structs: Useful when field names are unnecessary
     // ======================================================
 
+    #[allow(dead_code)]
     struct Color(i32, i32, i32);
     struct Point(i32, i32, i32);
 
+    #[allow(unused_variables)]
     let black = Color(0, 0, 0);
     let origin = Point(0, 0, 0);
 
@@ -70,6 +76,7 @@ pub fn ch5_1() {
     // let c: Color = origin; // ❌ Error
 
     // Destructuring tuple structs
+    #[allow(unused_variables)]
     let Point(x, y, z) = origin;
 
     // ======================================================
@@ -107,4 +114,5 @@ pub fn ch5_1() {
     */
 
     // Lifetimes tell Rust how long references are valid. (Covered in Chapter 10)
+    Ok(())
 }
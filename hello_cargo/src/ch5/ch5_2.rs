@@ -24,23 +24,27 @@
 // - Structs give names to the whole (Rectangle) and to each part (width, height).
 // - Improves code clarity and maintainability.
 
+use std::io::{self, Write};
+
 /// Rectangle struct definition
 struct Rectangle {
     width: u32,
     height: u32,
 }
 
-fn ch5_2_basic_struct_demo() {
+fn ch5_2_basic_struct_demo(out: &mut dyn Write) -> io::Result<()> {
     // Create a Rectangle instance
     let rect1 = Rectangle {
         width: 30,
         height: 50,
     };
 
-    println!(
+    writeln!(
+        out,
         "The area of the rectangle is {} square pixels.",
         area(&rect1)
-    );
+    )?;
+    Ok(())
 }
 
 /// Calculate area by borrowing a Rectangle struct.
@@ -55,22 +59,23 @@ fn area(rectangle: &Rectangle) -> u32 {
 // - Use {:?} or {:#?} in println! for debug formatting.
 
 #[derive(Debug)]
+#[allow(dead_code)] // the fields are only shown through Debug
 struct RectangleDebug {
     width: u32,
     height: u32,
 }
 
-fn ch5_2_debug_print_demo() {
+fn ch5_2_debug_print_demo(out: &mut dyn Write) -> io::Result<()> {
     let rect1 = RectangleDebug {
         width: 30,
         height: 50,
     };
 
     // Debug print (single line)
-    println!("rect1 is {:?}", rect1);
+    writeln!(out, "rect1 is {:?}", rect1)?;
 
     // Pretty debug print (multi-line)
-    println!("rect1 is {:#?}", rect1);
+    writeln!(out, "rect1 is {:#?}", rect1)?;
 
     // Using dbg! macro: prints to stderr with file/line info
     let scale = 2;
@@ -79,6 +84,7 @@ fn ch5_2_debug_print_demo() {
         height: 50,
     };
     dbg!(&rect2);
+    Ok(())
 }
 
 // -- Summary --
@@ -89,11 +95,12 @@ fn ch5_2_debug_print_demo() {
 // Next: Move the area function into a method on Rectangle for better encapsulation.
 
 /// Run all demos from this section
-pub fn ch5_2() {
-    println!("--- Rectangle struct area calculation ---");
-    ch5_2_basic_struct_demo();
+pub fn ch5_2(out: &mut dyn Write) -> io::Result<()> {
+    writeln!(out, "--- Rectangle struct area calculation ---")?;
+    ch5_2_basic_struct_demo(out)?;
 
-    println!("\n--- Debug printing Rectangle ---");
-    ch5_2_debug_print_demo();
+    writeln!(out, "\n--- Debug printing Rectangle ---")?;
+    ch5_2_debug_print_demo(out)?;
+    Ok(())
 }
 
@@ -1,3 +1,75 @@
 pub mod ch5_1;
 pub mod ch5_2;
-pub mod ch5_3;
\ No newline at end of file
+pub mod ch5_3;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch5_1;
+
+impl Chapter for Ch5_1 {
+    fn id(&self) -> &'static str {
+        "ch5_1"
+    }
+
+    fn title(&self) -> &'static str {
+        "Defining and Instantiating Structs"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &[
+            "structs",
+            "field init shorthand",
+            "struct update syntax",
+            "tuple structs",
+            "unit-like structs",
+        ]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch5_1::ch5_1(out)
+    }
+}
+
+pub struct Ch5_2;
+
+impl Chapter for Ch5_2 {
+    fn id(&self) -> &'static str {
+        "ch5_2"
+    }
+
+    fn title(&self) -> &'static str {
+        "An Example Program Using Structs"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["structs", "Debug", "dbg!"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch5_2::ch5_2(out)
+    }
+}
+
+pub struct Ch5_3;
+
+impl Chapter for Ch5_3 {
+    fn id(&self) -> &'static str {
+        "ch5_3"
+    }
+
+    fn title(&self) -> &'static str {
+        "Method Syntax"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["methods", "impl", "associated functions"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch5_3::ch5_3(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch5_1, &Ch5_2, &Ch5_3];
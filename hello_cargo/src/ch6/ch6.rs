@@ -24,17 +24,20 @@
 // 1. Why Enums? -- "A value of one of several possible kinds"
 // ===========================================================================
 
+use std::io::{self, Write};
+
 // Example: IP addresses are either V4 or V6 (never both at once)
-enum IpAddrKind {
+#[derive(Debug, PartialEq)]
+pub enum IpAddrKind {
     V4,
     V6,
 }
 
 // You can use enums in function parameters:
-fn route(ip_kind: IpAddrKind) {
+fn route(out: &mut dyn Write, ip_kind: IpAddrKind) -> io::Result<()> {
     match ip_kind {
-        IpAddrKind::V4 => println!("Routing IPv4 address..."),
-        IpAddrKind::V6 => println!("Routing IPv6 address..."),
+        IpAddrKind::V4 => writeln!(out, "Routing IPv4 address..."),
+        IpAddrKind::V6 => writeln!(out, "Routing IPv6 address..."),
     }
 }
 
@@ -43,9 +46,10 @@ fn route(ip_kind: IpAddrKind) {
 // ===========================================================================
 
 // Approach 1: struct + enum
-struct IpAddr {
-    kind: IpAddrKind,
-    address: String,
+#[derive(Debug, PartialEq)]
+pub struct IpAddr {
+    pub kind: IpAddrKind,
+    pub address: String,
 }
 
 // Approach 2: Data inside enum variant
@@ -55,6 +59,7 @@ enum IpAddr2 {
 }
 
 // Variants can have different data types
+#[allow(dead_code)]
 enum IpAddr3 {
     V4(u8, u8, u8, u8),
     V6(String),
@@ -63,8 +68,11 @@ enum IpAddr3 {
 // ===========================================================================
 // 3. Enum Variants Can Hold Any Type, Even Structs or Other Enums
 // ===========================================================================
+#[allow(dead_code)]
 struct Ipv4Addr {/* fields omitted */}
+#[allow(dead_code)]
 struct Ipv6Addr {/* fields omitted */}
+#[allow(dead_code)]
 enum StdIpAddr {
     V4(Ipv4Addr),
     V6(Ipv6Addr),
@@ -73,6 +81,7 @@ enum StdIpAddr {
 // ===========================================================================
 // 4. Example: Enum with Multiple Variant Types (like sum types)
 // ===========================================================================
+#[allow(dead_code)] // only Move is sent in the demo
 enum Message {
     Quit,
     Move { x: i32, y: i32 },
@@ -82,12 +91,12 @@ enum Message {
 
 // Methods can be implemented for enums too!
 impl Message {
-    fn call(&self) {
+    fn call(&self, out: &mut dyn Write) -> io::Result<()> {
         match self {
-            Message::Quit => println!("Quit message"),
-            Message::Move { x, y } => println!("Move to ({x}, {y})"),
-            Message::Write(text) => println!("Write message: {text}"),
-            Message::ChangeColor(r, g, b) => println!("Change color to ({r}, {g}, {b})"),
+            Message::Quit => writeln!(out, "Quit message"),
+            Message::Move { x, y } => writeln!(out, "Move to ({x}, {y})"),
+            Message::Write(text) => writeln!(out, "Write message: {text}"),
+            Message::ChangeColor(r, g, b) => writeln!(out, "Change color to ({r}, {g}, {b})"),
         }
     }
 }
@@ -95,12 +104,18 @@ impl Message {
 // ===========================================================================
 // 5. The Option<T> Enum: Rust's Safe Alternative to Null
 // ===========================================================================
-enum Option<T> {
-    None,
-    Some(T),
+// The standard library defines it like this (kept in its own module so it
+// doesn't shadow the prelude's Option for the rest of this file):
+#[allow(dead_code)]
+mod std_option {
+    pub enum Option<T> {
+        None,
+        Some(T),
+    }
 }
 
 // Rust's std library version of Option is used like this:
+#[allow(dead_code, unused_variables)]
 fn option_examples() {
     let some_number = Some(5);
     let some_char = Some('e');
@@ -115,6 +130,7 @@ fn option_examples() {
 // ===========================================================================
 // 6. Pattern Matching with match: Exhaustive, Safe, and Powerful
 // ===========================================================================
+#[allow(dead_code)]
 enum Coin {
     Penny,
     Nickel,
@@ -139,6 +155,7 @@ enum UsState {
     // ... more states can be added
 }
 
+#[allow(dead_code)]
 enum Coin2 {
     Penny,
     Nickel,
@@ -146,19 +163,21 @@ enum Coin2 {
     Quarter(UsState),
 }
 
-fn value_in_cents2(coin: Coin2) -> u8 {
-    match coin {
+fn value_in_cents2(out: &mut dyn Write, coin: Coin2) -> io::Result<u8> {
+    let cents = match coin {
         Coin2::Penny => 1,
         Coin2::Nickel => 5,
         Coin2::Dime => 10,
         Coin2::Quarter(state) => {
-            println!("State quarter from {state:?}!");
+            writeln!(out, "State quarter from {state:?}!")?;
             25
         }
-    }
+    };
+    Ok(cents)
 }
 
 // Matching Option<T> to extract and transform values
+#[allow(clippy::manual_map)] // spelled out to show matching on Option
 fn plus_one(x: Option<i32>) -> Option<i32> {
     match x {
         None => None,
@@ -208,16 +227,18 @@ fn reroll() {}
 // 8. Concise Control Flow: if let and let ... else
 // ===========================================================================
 
-fn concise_match_examples() {
+#[allow(unused_variables, unused_assignments)] // `count` is only ever counted up
+fn concise_match_examples(out: &mut dyn Write) -> io::Result<()> {
     // if let: concise single-pattern match for Option and enums
     let config_max = Some(3u8);
     if let Some(max) = config_max {
-        println!("The maximum is configured to be {max}");
+        writeln!(out, "The maximum is configured to be {max}")?;
     }
 
     // Equivalent to:
+    #[allow(clippy::single_match)]
     match config_max {
-        Some(max) => println!("The maximum is configured to be {max}"),
+        Some(max) => writeln!(out, "The maximum is configured to be {max}")?,
         _ => (),
     }
 
@@ -225,7 +246,7 @@ fn concise_match_examples() {
     let mut count = 0;
     let coin = Coin2::Quarter(UsState::Alaska);
     if let Coin2::Quarter(state) = coin {
-        println!("State quarter from {state:?}!");
+        writeln!(out, "State quarter from {state:?}!")?;
     } else {
         count += 1;
     }
@@ -233,7 +254,8 @@ fn concise_match_examples() {
     // let ... else: for "happy path" and early return
     let coin2 = Coin2::Quarter(UsState::Alabama);
     let description = describe_state_quarter(coin2);
-    println!("Description: {:?}", description);
+    writeln!(out, "Description: {:?}", description)?;
+    Ok(())
 }
 
 fn describe_state_quarter(coin: Coin2) -> Option<String> {
@@ -251,47 +273,50 @@ fn describe_state_quarter(coin: Coin2) -> Option<String> {
 // Code Demo: Enums, Pattern Matching, Option
 // ===========================================================================
 
-pub fn ch6() {
+pub fn ch6(out: &mut dyn Write) -> io::Result<()> {
     // Basic enum usage
     let four = IpAddrKind::V4;
     let six = IpAddrKind::V6;
-    route(four);
-    route(six);
+    route(out, four)?;
+    route(out, six)?;
 
     // Enum with data
     let home = IpAddr2::V4(String::from("127.0.0.1"));
     let loopback = IpAddr2::V6(String::from("::1"));
     match home {
-        IpAddr2::V4(addr) => println!("Home IPv4: {addr}"),
-        IpAddr2::V6(addr) => println!("Home IPv6: {addr}"),
+        IpAddr2::V4(addr) => writeln!(out, "Home IPv4: {addr}")?,
+        IpAddr2::V6(addr) => writeln!(out, "Home IPv6: {addr}")?,
     }
     match loopback {
-        IpAddr2::V4(addr) => println!("Loopback IPv4: {addr}"),
-        IpAddr2::V6(addr) => println!("Loopback IPv6: {addr}"),
+        IpAddr2::V4(addr) => writeln!(out, "Loopback IPv4: {addr}")?,
+        IpAddr2::V6(addr) => writeln!(out, "Loopback IPv6: {addr}")?,
     }
 
     // Option<T>
     let five = Some(5);
     let six = plus_one(five);
     let none = plus_one(None);
-    println!("six: {:?}, none: {:?}", six, none);
+    writeln!(out, "six: {:?}, none: {:?}", six, none)?;
 
     // match with Coin
     let c = Coin::Penny;
-    println!("Penny value: {}", value_in_cents(c));
+    writeln!(out, "Penny value: {}", value_in_cents(c))?;
 
     // match with Coin2 and UsState
     let quarter = Coin2::Quarter(UsState::Alaska);
-    println!("Quarter value: {}", value_in_cents2(quarter));
+    let cents = value_in_cents2(out, quarter)?;
+    writeln!(out, "Quarter value: {cents}")?;
 
     // Message enum method
     let msg = Message::Move { x: 10, y: 20 };
-    msg.call();
+    msg.call(out)?;
 
     // Demo concise matching
-    concise_match_examples();
+    concise_match_examples(out)?;
 
     // Demo match catchall
     match_catchall_examples();
+
+    Ok(())
 }
 
@@ -1 +1,29 @@
-pub mod ch6;
\ No newline at end of file
+#![allow(clippy::module_inception)]
+
+pub mod ch6;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch6;
+
+impl Chapter for Ch6 {
+    fn id(&self) -> &'static str {
+        "ch6"
+    }
+
+    fn title(&self) -> &'static str {
+        "Enums and Pattern Matching"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["enums", "Option", "match", "if let", "let else"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch6::ch6(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch6];
@@ -1,6 +1,8 @@
 // Chapter 7: Modules, Crates, Packages - Detailed Guide
 
-pub fn ch7() {
+use std::io::{self, Write};
+
+pub fn ch7(out: &mut dyn Write) -> io::Result<()> {
     // 1. Crates & Packages
     // ---------------------
     // - Crate: Smallest compilation unit in Rust.
@@ -138,5 +140,6 @@ pub fn ch7() {
     // use std::collections::HashMap;
     // let mut map = HashMap::new();
 
-    println!("Detailed module system notes and idiomatic patterns available in source.");
+    writeln!(out, "Detailed module system notes and idiomatic patterns available in source.")?;
+    Ok(())
 }
\ No newline at end of file
@@ -1,2 +1,29 @@
+#![allow(clippy::module_inception)]
 
-pub mod ch7;
\ No newline at end of file
+pub mod ch7;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch7;
+
+impl Chapter for Ch7 {
+    fn id(&self) -> &'static str {
+        "ch7"
+    }
+
+    fn title(&self) -> &'static str {
+        "Packages, Crates, and Modules"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["modules", "crates", "packages", "paths", "privacy", "use"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch7::ch7(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch7];
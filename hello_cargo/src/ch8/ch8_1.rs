@@ -1,6 +1,8 @@
 // Chapter 8 (Section: Vectors) - Notes & Examples
 
-pub fn ch8_1() {
+use std::io::{self, Write};
+
+pub fn ch8_1(out: &mut dyn Write) -> io::Result<()> {
     // 1. What is a Vector?
     // --------------------
     // - Vec<T> is a growable list type storing values of type T, contiguously in memory.
@@ -9,29 +11,35 @@ pub fn ch8_1() {
     // 2. Creating Vectors
     // -------------------
     // Empty with explicit type:
+    #[allow(unused_variables)] // only shows the type annotation
     let v1: Vec<i32> = Vec::new();
     // With initial values (type inferred):
+    #[allow(unused_variables, clippy::useless_vec)] // the `vec!` macro is the point here
     let v2 = vec![1, 2, 3];
 
     // 3. Updating a Vector
     // --------------------
     // Use `push` to append elements.
-    let mut v3 = Vec::new();
-    v3.push(5);
-    v3.push(6);
+    #[allow(clippy::vec_init_then_push)] // the book builds it up with `push`
+    {
+        let mut v3 = Vec::new();
+        v3.push(5);
+        v3.push(6);
+    }
 
     // 4. Reading Elements
     // -------------------
+    #[allow(clippy::useless_vec)] // kept as a Vec to show indexing and `get`
     let v = vec![1, 2, 3, 4, 5];
     // (a) Indexing (panics if out of bounds)
     let third: &i32 = &v[2];
-    println!("The third element is {third}");
+    writeln!(out, "The third element is {third}")?;
 
     // (b) get() method (safe, returns Option)
     let third = v.get(2);
     match third {
-        Some(val) => println!("The third element is {val}"),
-        None => println!("There is no third element."),
+        Some(val) => writeln!(out, "The third element is {val}")?,
+        None => writeln!(out, "There is no third element.")?,
     }
 
     // (c) Out of bounds
@@ -49,21 +57,18 @@ pub fn ch8_1() {
     // -------------------------
     let v = vec![100, 32, 57];
     for i in &v {
-        println!("Immutable ref: {i}");
+        writeln!(out, "Immutable ref: {i}")?;
     }
     let mut v = vec![100, 32, 57];
     for i in &mut v {
         *i += 50;
     }
-    println!("After mutation: {:?}", v);
+    writeln!(out, "After mutation: {:?}", v)?;
 
     // 7. Vectors of Multiple Types with Enums
     // ---------------------------------------
-    enum SpreadsheetCell {
-        Int(i32),
-        Float(f64),
-        Text(String),
-    }
+    // (SpreadsheetCell is defined below the function so other code can use it.)
+    #[allow(unused_variables, clippy::useless_vec)] // one Vec holding every kind of cell
     let row = vec![
         SpreadsheetCell::Int(3),
         SpreadsheetCell::Text(String::from("blue")),
@@ -73,6 +78,7 @@ pub fn ch8_1() {
     // 8. Dropping Vectors
     // -------------------
     {
+        #[allow(unused_variables, clippy::useless_vec)] // only here to be dropped
         let v = vec![1, 2, 3, 4];
         // v and its contents are dropped at end of scope
     }
@@ -82,5 +88,14 @@ pub fn ch8_1() {
     // - Use Vec<T> when you need a growable, ordered list.
     // - Many methods: pop(), len(), is_empty(), sort(), etc. (see Vec<T> docs)
 
-    println!("See source for notes and examples on vectors (Vec<T>) in Rust.");
-}
\ No newline at end of file
+    writeln!(out, "See source for notes and examples on vectors (Vec<T>) in Rust.")?;
+    Ok(())
+}
+
+/// A vector can only hold one type, so an enum lets one row mix cell kinds.
+#[derive(Debug, PartialEq)]
+pub enum SpreadsheetCell {
+    Int(i32),
+    Float(f64),
+    Text(String),
+}
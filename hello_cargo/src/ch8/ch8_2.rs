@@ -1,6 +1,8 @@
 // Chapter 8.2: Storing UTF-8 Encoded Text with Strings - Notes & Examples
 
-pub fn ch8_2() {
+use std::io::{self, Write};
+
+pub fn ch8_2(out: &mut dyn Write) -> io::Result<()> {
     // 1. What is a "String" in Rust?
     // ------------------------------
     // - String: growable, mutable, owned, UTF-8 encoded text.
@@ -9,16 +11,23 @@ pub fn ch8_2() {
 
     // 2. Creating Strings
     // -------------------
+    #[allow(unused_variables, unused_mut)] // an empty String to push onto later
     let mut s = String::new(); // empty String
 
     let data = "initial contents";
+    #[allow(unused_variables)] // three ways to the same String
     let s1 = data.to_string();
+    #[allow(unused_variables)] // same as `s1`
     let s2 = "initial contents".to_string();
+    #[allow(unused_variables)] // same as `s1`
     let s3 = String::from("initial contents");
 
     // UTF-8: Can store text in any language!
+    #[allow(unused_variables)] // any UTF-8 text will do
     let hello = String::from("नमस्ते");
+    #[allow(unused_variables)] // shadows the previous greeting
     let hello = String::from("Здравствуйте");
+    #[allow(unused_variables)] // shadows the previous greeting
     let hello = String::from("Hello");
 
     // 3. Updating/Modifying Strings
@@ -34,12 +43,14 @@ pub fn ch8_2() {
     // (c) Concatenation with +
     let s1 = String::from("Hello, ");
     let s2 = String::from("world!");
+    #[allow(unused_variables)] // only `s1` being moved matters
     let s3 = s1 + &s2; // s1 is moved, s3 == "Hello, world!"
 
     // (d) format! macro (recommended for multiple strings)
     let s1 = String::from("tic");
     let s2 = String::from("tac");
     let s3 = String::from("toe");
+    #[allow(unused_variables)] // shows what `format!` builds
     let s = format!("{s1}-{s2}-{s3}"); // s == "tic-tac-toe"
     // format! does NOT take ownership
 
@@ -55,10 +66,11 @@ pub fn ch8_2() {
 
     // - Length in bytes != number of characters
     let hello = String::from("Здравствуйте");
-    println!("Length = {}", hello.len()); // 24 bytes, not 12 chars!
+    writeln!(out, "Length = {}", hello.len())?; // 24 bytes, not 12 chars!
 
     // - String slices must be on char boundaries, otherwise program panics:
     let hello = "Здравствуйте";
+    #[allow(unused_variables)] // only shows a valid slice
     let s = &hello[0..4]; // valid, s == "Зд"
     // let bad = &hello[0..1]; // panics at runtime!
 
@@ -66,13 +78,13 @@ pub fn ch8_2() {
     // -------------------------
     // - .chars(): iterate over Unicode scalar values (char)
     for c in "Зд".chars() {
-        println!("{c}");
+        writeln!(out, "{c}")?;
     }
     // Output: З д
 
     // - .bytes(): iterate over raw bytes
     for b in "Зд".bytes() {
-        println!("{b}");
+        writeln!(out, "{b}")?;
     }
     // Output: 208 151 208 180 (raw byte values)
 
@@ -85,5 +97,6 @@ pub fn ch8_2() {
     // - Use .chars(), .bytes(), and string slices carefully.
     // - Many useful methods: contains, replace, etc.
 
-    println!("See source for notes and examples on UTF-8 Strings in Rust.");
+    writeln!(out, "See source for notes and examples on UTF-8 Strings in Rust.")?;
+    Ok(())
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 // Chapter 8.3: Storing Keys with Associated Values in Hash Maps - Notes & Examples
 
-pub fn ch8_3() {
+use std::io::{self, Write};
+
+pub fn ch8_3(out: &mut dyn Write) -> io::Result<()> {
     // 1. What is a HashMap?
     // ---------------------
     // - HashMap<K, V>: Stores key-value pairs using a hash function.
@@ -18,13 +20,14 @@ pub fn ch8_3() {
     // 3. Accessing Values
     // -------------------
     let team_name = String::from("Blue");
+    #[allow(unused_variables)]
     let score = scores.get(&team_name).copied().unwrap_or(0);
     // .get() returns Option<&V>. Use .copied() if V: Copy, and .unwrap_or(default) to handle missing keys.
 
     // 4. Iterating
     // ------------
     for (key, value) in &scores {
-        println!("{key}: {value}");
+        writeln!(out, "{key}: {value}")?;
     }
 
     // 5. Ownership
@@ -57,7 +60,7 @@ pub fn ch8_3() {
         let count = map.entry(word).or_insert(0);
         *count += 1;
     }
-    println!("{map:?}"); // e.g., {"world": 2, "hello": 1, "wonderful": 1}
+    writeln!(out, "{map:?}")?; // e.g., {"world": 2, "hello": 1, "wonderful": 1}
 
     // 7. Hashing Functions
     // --------------------
@@ -74,5 +77,6 @@ pub fn ch8_3() {
     // - Pig Latin conversion (string manipulation)
     // - Company directory by department (hash map + vectors)
 
-    println!("See source for notes and examples on HashMap<K, V> in Rust.");
+    writeln!(out, "See source for notes and examples on HashMap<K, V> in Rust.")?;
+    Ok(())
 }
\ No newline at end of file
@@ -0,0 +1,69 @@
+pub mod ch8_1;
+pub mod ch8_2;
+pub mod ch8_3;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch8_1;
+
+impl Chapter for Ch8_1 {
+    fn id(&self) -> &'static str {
+        "ch8_1"
+    }
+
+    fn title(&self) -> &'static str {
+        "Storing Lists of Values with Vectors"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["Vec", "vec!", "indexing", "get", "iteration", "enums in vectors"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch8_1::ch8_1(out)
+    }
+}
+
+pub struct Ch8_2;
+
+impl Chapter for Ch8_2 {
+    fn id(&self) -> &'static str {
+        "ch8_2"
+    }
+
+    fn title(&self) -> &'static str {
+        "Storing UTF-8 Encoded Text with Strings"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["String", "push_str", "concatenation", "format!", "UTF-8", "chars", "bytes"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch8_2::ch8_2(out)
+    }
+}
+
+pub struct Ch8_3;
+
+impl Chapter for Ch8_3 {
+    fn id(&self) -> &'static str {
+        "ch8_3"
+    }
+
+    fn title(&self) -> &'static str {
+        "Storing Keys with Associated Values in Hash Maps"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["HashMap", "insert", "entry", "or_insert", "ownership"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch8_3::ch8_3(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch8_1, &Ch8_2, &Ch8_3];
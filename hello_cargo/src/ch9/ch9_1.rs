@@ -1,6 +1,8 @@
 // Chapter 9.1: Unrecoverable Errors with panic! - Notes & Examples
 
-pub fn c9_1() {
+use std::io::{self, Write};
+
+pub fn c9_1(out: &mut dyn Write) -> io::Result<()> {
     // 1. What is panic!?
     // ------------------
     // - Rust's way of handling unrecoverable errors.
@@ -23,6 +25,7 @@ pub fn c9_1() {
     // panic!("crash and burn");
 
     // 4. Example: Panic from a library (out-of-bounds)
+    #[allow(unused_variables, clippy::useless_vec)]
     let v = vec![1, 2, 3];
     // v[99]; // Panics: index out of bounds
 
@@ -48,5 +51,6 @@ pub fn c9_1() {
     // - Only in situations where your code cannot recover (program invariants broken, bugs, etc.).
     // - For recoverable errors, use Result (see next section).
 
-    println!("See source for notes and examples on unrecoverable errors and panic! in Rust.");
+    writeln!(out, "See source for notes and examples on unrecoverable errors and panic! in Rust.")?;
+    Ok(())
 }
\ No newline at end of file
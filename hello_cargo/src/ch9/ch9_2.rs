@@ -1,6 +1,8 @@
 // Chapter 9.2: Recoverable Errors with Result - Notes & Examples
 
-pub fn ch9_2() {
+use std::io::{self, Write};
+
+pub fn ch9_2(out: &mut dyn Write) -> io::Result<()> {
     // 1. What is Result?
     // ------------------
     // - Enum for recoverable errors.
@@ -17,6 +19,7 @@ pub fn ch9_2() {
 
     // 3. Handling Result with match
     // -----------------------------
+    #[allow(unused_variables)] // each way of opening the file rebinds this name
     let greeting_file = match greeting_file_result {
         Ok(file) => file,
         Err(error) => panic!("Problem opening the file: {error:?}"),
@@ -25,6 +28,7 @@ pub fn ch9_2() {
     // 4. Matching on Specific Errors
     // ------------------------------
     use std::io::ErrorKind;
+    #[allow(unused_variables)] // opened or created, then unused
     let greeting_file = match File::open("hello.txt") {
         Ok(file) => file,
         Err(error) => match error.kind() {
@@ -38,6 +42,7 @@ pub fn ch9_2() {
 
     // 5. Alternatives: Closures and Helper Methods
     // --------------------------------------------
+    #[allow(unused_variables)] // the closure version of the match above
     let greeting_file = File::open("hello.txt").unwrap_or_else(|error| {
         if error.kind() == ErrorKind::NotFound {
             File::create("hello.txt").unwrap_or_else(|error| {
@@ -52,13 +57,16 @@ pub fn ch9_2() {
     // -----------------------------
     // .unwrap() => Ok(T) returns T, Err(E) panics.
     // .expect("msg") => Ok(T) returns T, Err(E) panics with "msg".
+    #[allow(unused_variables)] // the unwrap shortcut
     let greeting_file = File::open("hello.txt").unwrap();
+    #[allow(unused_variables)] // the expect shortcut
     let greeting_file = File::open("hello.txt")
         .expect("hello.txt should be included in this project");
 
     // 7. Propagating Errors (returning Result)
     // ----------------------------------------
     use std::io::{self, Read};
+    #[allow(dead_code)] // the long form of `?`; the demo never reads hello.txt
     fn read_username_from_file() -> Result<String, io::Error> {
         let mut username_file = File::open("hello.txt")?;
         let mut username = String::new();
@@ -70,6 +78,7 @@ pub fn ch9_2() {
     // -----------------
     // - Returns early if Err, otherwise unwraps Ok.
     // - Chaining:
+    #[allow(dead_code)] // the chained form
     fn read_username_from_file_short() -> Result<String, io::Error> {
         let mut username = String::new();
         File::open("hello.txt")?.read_to_string(&mut username)?;
@@ -78,12 +87,14 @@ pub fn ch9_2() {
 
     // Even shorter using std::fs::read_to_string:
     use std::fs;
+    #[allow(dead_code)] // the shortest form
     fn read_username_from_file_shortest() -> Result<String, io::Error> {
         fs::read_to_string("hello.txt")
     }
 
     // 9. ? Operator with Option
     // -------------------------
+    #[allow(dead_code)] // `?` on Option, not called
     fn last_char_of_first_line(text: &str) -> Option<char> {
         text.lines().next()?.chars().last()
     }
@@ -92,10 +103,13 @@ pub fn ch9_2() {
     // ---------------------
     // - main can return Result<(), E> for ? operator use.
     use std::error::Error;
+    #[allow(dead_code)] // a `main` that returns Result, never run
     fn main() -> Result<(), Box<dyn Error>> {
+        #[allow(unused_variables)] // only the `?` matters
         let greeting_file = File::open("hello.txt")?;
         Ok(())
     }
 
-    println!("See source for notes and examples on recoverable errors and Result in Rust.");
+    writeln!(out, "See source for notes and examples on recoverable errors and Result in Rust.")?;
+    Ok(())
 }
\ No newline at end of file
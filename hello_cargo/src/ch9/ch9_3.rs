@@ -1,6 +1,11 @@
 // Chapter 9.3: To panic! or Not to panic! - Notes & Guidelines
 
-pub fn ch9_3() {
+use std::error::Error;
+use std::fmt;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+pub fn ch9_3(out: &mut dyn Write) -> io::Result<()> {
     // 1. When to panic! vs. when to return Result
     // -------------------------------------------
     // - panic! is for unrecoverable errors; you give no option for recovery.
@@ -12,6 +17,7 @@ pub fn ch9_3() {
     // - Example/prototype/test code (unwrap/expect is fine for brevity and clarity).
     // - When logic guarantees Ok, but compiler can't verify it (e.g. hardcoded valid inputs):
     use std::net::IpAddr;
+    #[allow(unused_variables)]
     let home: IpAddr = "127.0.0.1".parse().expect("Hardcoded IP address should be valid");
 
     // 3. Guidelines for panic!
@@ -30,22 +36,7 @@ pub fn ch9_3() {
     // -------------------------------------
     // - Prefer encoding invariants in types (e.g. unsigned types, custom structs) to avoid runtime checks.
     // - Example: Use a custom type to guarantee a value is in a valid range.
-    pub struct Guess {
-        value: i32,
-    }
-
-    impl Guess {
-        pub fn new(value: i32) -> Guess {
-            if value < 1 || value > 100 {
-                panic!("Guess value must be between 1 and 100, got {value}.");
-            }
-            Guess { value }
-        }
-
-        pub fn value(&self) -> i32 {
-            self.value
-        }
-    }
+    //   (see `Guess` below, which can only hold a value from 1 to 100)
 
     // 6. Summary
     // ----------
@@ -54,5 +45,54 @@ pub fn ch9_3() {
     // - Use the type system to prevent invalid states.
     // - Document panics in your API.
 
-    println!("See source for guidelines and examples on panic! vs Result error-handling choices in Rust.");
-}
\ No newline at end of file
+    writeln!(out, "See source for guidelines and examples on panic! vs Result error-handling choices in Rust.")?;
+    Ok(())
+}
+
+/// A guess in the range 1..=100; `Guess::new` panics on anything else.
+pub struct Guess {
+    value: i32,
+}
+
+impl Guess {
+    pub fn new(value: i32) -> Guess {
+        Guess::in_range(value, 1..=100).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// A guess in `range`, or an error for anything else. Unlike `new`, this
+    /// is for values typed by a user, where being out of range isn't a bug.
+    pub fn in_range(value: i32, range: RangeInclusive<i32>) -> Result<Guess, OutOfRange> {
+        if !range.contains(&value) {
+            return Err(OutOfRange {
+                value,
+                min: *range.start(),
+                max: *range.end(),
+            });
+        }
+        Ok(Guess { value })
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+/// Why `Guess::in_range` refused a value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfRange {
+    pub value: i32,
+    pub min: i32,
+    pub max: i32,
+}
+
+impl fmt::Display for OutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Guess value must be between {} and {}, got {}.",
+            self.min, self.max, self.value
+        )
+    }
+}
+
+impl Error for OutOfRange {}
@@ -1,3 +1,69 @@
 pub mod ch9_3;
 pub mod ch9_2;
-pub mod ch9_1;
\ No newline at end of file
+pub mod ch9_1;
+
+use std::io::{self, Write};
+
+use crate::chapter::Chapter;
+
+pub struct Ch9_1;
+
+impl Chapter for Ch9_1 {
+    fn id(&self) -> &'static str {
+        "ch9_1"
+    }
+
+    fn title(&self) -> &'static str {
+        "Unrecoverable Errors with panic!"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["panic!", "unwinding", "backtrace"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch9_1::c9_1(out)
+    }
+}
+
+pub struct Ch9_2;
+
+impl Chapter for Ch9_2 {
+    fn id(&self) -> &'static str {
+        "ch9_2"
+    }
+
+    fn title(&self) -> &'static str {
+        "Recoverable Errors with Result"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["Result", "unwrap", "expect", "? operator", "error propagation"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch9_2::ch9_2(out)
+    }
+}
+
+pub struct Ch9_3;
+
+impl Chapter for Ch9_3 {
+    fn id(&self) -> &'static str {
+        "ch9_3"
+    }
+
+    fn title(&self) -> &'static str {
+        "To panic! or Not to panic!"
+    }
+
+    fn topics(&self) -> &'static [&'static str] {
+        &["panic!", "Result", "validation"]
+    }
+
+    fn run(&self, out: &mut dyn Write) -> io::Result<()> {
+        ch9_3::ch9_3(out)
+    }
+}
+
+pub const SECTIONS: &[&dyn Chapter] = &[&Ch9_1, &Ch9_2, &Ch9_3];
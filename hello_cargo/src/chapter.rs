@@ -0,0 +1,72 @@
+// Chapter registry: every module under src/chN/ describes its sections with
+// the Chapter trait and lists them in a `SECTIONS` table, so menus, tests and
+// docs tooling can enumerate the demos without hard-coding function paths.
+
+use std::io::{self, Write};
+
+use crate::{
+    ch1, ch2, ch3, ch4, ch5, ch6, ch7, ch8, ch9, ch10, ch11, ch12, ch13, ch14, ch15, ch16, ch17,
+};
+
+/// A runnable chapter (or chapter section) demo.
+pub trait Chapter: Sync {
+    /// Identifier used on the command line, e.g. `ch4_2`.
+    fn id(&self) -> &'static str;
+
+    /// Section title as it appears in the book.
+    fn title(&self) -> &'static str;
+
+    /// Keywords covered by the demo, used for searching.
+    fn topics(&self) -> &'static [&'static str];
+
+    /// Chapters that wait on stdin are skipped by `run --all`.
+    fn interactive(&self) -> bool {
+        false
+    }
+
+    /// Runs the demo, writing its output to `out`.
+    fn run(&self, out: &mut dyn Write) -> io::Result<()>;
+}
+
+/// Sections of each chapter module, in book order.
+const MODULES: &[&[&dyn Chapter]] = &[
+    ch1::SECTIONS,
+    ch2::SECTIONS,
+    ch3::SECTIONS,
+    ch4::SECTIONS,
+    ch5::SECTIONS,
+    ch6::SECTIONS,
+    ch7::SECTIONS,
+    ch8::SECTIONS,
+    ch9::SECTIONS,
+    ch10::SECTIONS,
+    ch11::SECTIONS,
+    ch12::SECTIONS,
+    ch13::SECTIONS,
+    ch14::SECTIONS,
+    ch15::SECTIONS,
+    ch16::SECTIONS,
+    ch17::SECTIONS,
+];
+
+/// Every registered chapter, in book order.
+pub fn all() -> impl Iterator<Item = &'static dyn Chapter> {
+    MODULES.iter().flat_map(|sections| sections.iter().copied())
+}
+
+/// Looks a chapter up by its id.
+pub fn find(id: &str) -> Option<&'static dyn Chapter> {
+    all().find(|chapter| chapter.id() == id)
+}
+
+/// Chapters whose title or topics mention `term` (case-insensitive).
+pub fn search(term: &str) -> impl Iterator<Item = &'static dyn Chapter> {
+    let term = term.to_lowercase();
+    all().filter(move |chapter| {
+        chapter.title().to_lowercase().contains(&term)
+            || chapter
+                .topics()
+                .iter()
+                .any(|topic| topic.to_lowercase().contains(&term))
+    })
+}
@@ -0,0 +1,352 @@
+// A small command-line parser shared by the hello_cargo commands, minigrep
+// and the guessing game among them, in the style of getopt_long.
+//
+// Options are described by a table of `Opt`s, which drives both parsing and
+// the generated `--help` text. Parsing accepts:
+//
+//   -i -w, -iw          short flags, alone or bundled
+//   -C 2, -C2           short options with a value, separate or attached
+//   --context 2         long options with a value, separate
+//   --context=2         or after `=`
+//   --                  everything after it is positional, even `-x`
+//   -                   a positional argument (standard input, for minigrep)
+
+use std::error::Error;
+use std::fmt;
+use std::process;
+
+/// One option the parser understands.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Opt {
+    pub short: Option<char>,
+    pub long: &'static str,
+    /// Another long name for the same option, not shown in the help text.
+    pub alias: Option<&'static str>,
+    /// The value's name in the help text, for options that take one.
+    pub value: Option<&'static str>,
+    pub help: &'static str,
+}
+
+impl Opt {
+    /// A flag with a long name and, optionally, a short one.
+    pub const fn new(short: Option<char>, long: &'static str, help: &'static str) -> Opt {
+        Opt {
+            short,
+            long,
+            alias: None,
+            value: None,
+            help,
+        }
+    }
+
+    /// The same option, taking a value called `name`.
+    pub const fn value(self, name: &'static str) -> Opt {
+        Opt {
+            value: Some(name),
+            ..self
+        }
+    }
+
+    /// The same option, also accepted as `--alias`.
+    pub const fn alias(self, alias: &'static str) -> Opt {
+        Opt {
+            alias: Some(alias),
+            ..self
+        }
+    }
+}
+
+/// An option found on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Found {
+    pub opt: &'static Opt,
+    /// The option as written, like `-C` or `--context`, for error messages.
+    pub name: String,
+    /// Present exactly when `opt` takes a value.
+    pub value: Option<String>,
+}
+
+/// The options, in command-line order, and the positional arguments.
+#[derive(Debug, Default, PartialEq)]
+pub struct Parsed {
+    pub options: Vec<Found>,
+    pub positional: Vec<String>,
+}
+
+/// Why the command line couldn't be used.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgsError {
+    /// `--help` was given; the usage text should be printed instead.
+    Help,
+    /// `--version` was given.
+    Version,
+    UnknownOption(String),
+    MissingValue(String),
+    /// A flag was given a value, as in `--ignore-case=yes`.
+    UnexpectedValue(String),
+    InvalidValue {
+        option: String,
+        value: String,
+        expected: &'static str,
+    },
+    /// A glob or regular expression that doesn't compile, with the reason.
+    InvalidPattern(String),
+    /// A config file that can't be read or parsed, with the reason.
+    InvalidConfig(String),
+    /// An option that only works together with another one.
+    Requires {
+        option: &'static str,
+        needs: &'static str,
+    },
+    /// Two options that can't be used together.
+    Conflicts {
+        option: &'static str,
+        with: &'static str,
+    },
+    MissingQuery,
+    /// A required positional argument, like `<dir>`, is missing.
+    MissingArgument(&'static str),
+    UnexpectedArgument(String),
+}
+
+impl ArgsError {
+    /// The process exit code for this error: 0 for help and version, 2 for
+    /// actual errors, as grep does.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            ArgsError::Help | ArgsError::Version => 0,
+            _ => 2,
+        }
+    }
+}
+
+impl fmt::Display for ArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArgsError::Help => write!(f, "help requested"),
+            ArgsError::Version => write!(f, "version requested"),
+            ArgsError::UnknownOption(option) => write!(f, "unknown option `{option}`"),
+            ArgsError::MissingValue(option) => write!(f, "{option} needs a value"),
+            ArgsError::UnexpectedValue(option) => write!(f, "{option} doesn't take a value"),
+            ArgsError::InvalidValue {
+                option,
+                value,
+                expected,
+            } => write!(
+                f,
+                "invalid value `{value}` for {option}; expected {expected}"
+            ),
+            ArgsError::InvalidPattern(reason) | ArgsError::InvalidConfig(reason) => {
+                write!(f, "{reason}")
+            }
+            ArgsError::Requires { option, needs } => write!(f, "{option} needs {needs}"),
+            ArgsError::Conflicts { option, with } => {
+                write!(f, "{option} can't be used with {with}")
+            }
+            ArgsError::MissingQuery => write!(f, "not enough arguments; missing <query>"),
+            ArgsError::MissingArgument(name) => write!(f, "not enough arguments; missing {name}"),
+            ArgsError::UnexpectedArgument(arg) => write!(f, "unexpected argument `{arg}`"),
+        }
+    }
+}
+
+impl Error for ArgsError {}
+
+/// Splits `args` (without the program name) into options from `opts` and
+/// positional arguments.
+pub fn parse(opts: &'static [Opt], args: &[String]) -> Result<Parsed, ArgsError> {
+    let mut parsed = Parsed::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            parsed.positional.extend(args.cloned());
+            break;
+        }
+
+        if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline_value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            let opt = opts
+                .iter()
+                .find(|opt| opt.long == name || opt.alias == Some(name))
+                .ok_or_else(|| ArgsError::UnknownOption(format!("--{name}")))?;
+            let name = format!("--{name}");
+            let value = match (opt.value, inline_value) {
+                (Some(_), Some(value)) => Some(value),
+                (Some(_), None) => Some(
+                    args.next()
+                        .cloned()
+                        .ok_or_else(|| ArgsError::MissingValue(name.clone()))?,
+                ),
+                (None, Some(_)) => return Err(ArgsError::UnexpectedValue(name)),
+                (None, None) => None,
+            };
+            parsed.options.push(Found { opt, name, value });
+        } else if let Some(shorts) = arg.strip_prefix('-').filter(|rest| !rest.is_empty()) {
+            for (i, c) in shorts.char_indices() {
+                let opt = opts
+                    .iter()
+                    .find(|opt| opt.short == Some(c))
+                    .ok_or_else(|| ArgsError::UnknownOption(format!("-{c}")))?;
+                let name = format!("-{c}");
+                if opt.value.is_none() {
+                    parsed.options.push(Found {
+                        opt,
+                        name,
+                        value: None,
+                    });
+                    continue;
+                }
+                // The rest of the bundle, if any, is the value.
+                let attached = &shorts[i + c.len_utf8()..];
+                let value = if attached.is_empty() {
+                    args.next()
+                        .cloned()
+                        .ok_or_else(|| ArgsError::MissingValue(name.clone()))?
+                } else {
+                    attached.to_string()
+                };
+                parsed.options.push(Found {
+                    opt,
+                    name,
+                    value: Some(value),
+                });
+                break;
+            }
+        } else {
+            parsed.positional.push(arg.clone());
+        }
+    }
+
+    Ok(parsed)
+}
+
+/// The options section of the help text, one aligned line per option.
+pub fn describe(opts: &[Opt]) -> String {
+    let names: Vec<String> = opts
+        .iter()
+        .map(|opt| {
+            let short = match opt.short {
+                Some(c) => format!("-{c}, "),
+                None => String::from("    "),
+            };
+            match opt.value {
+                Some(value) => format!("{short}--{} <{value}>", opt.long),
+                None => format!("{short}--{}", opt.long),
+            }
+        })
+        .collect();
+    let width = names.iter().map(String::len).max().unwrap_or(0);
+
+    names
+        .iter()
+        .zip(opts)
+        .map(|(name, opt)| format!("  {name:<width$}  {}\n", opt.help))
+        .collect()
+}
+
+/// Exits after a command-line error: prints the help text from `usage` for
+/// `ArgsError::Help`, `command` and the crate's version for
+/// `ArgsError::Version`, otherwise the error and a hint to run `hello_cargo
+/// <command> --help`.
+pub fn exit_with(err: ArgsError, usage: fn() -> String, command: &str) -> ! {
+    match err {
+        ArgsError::Help => print!("{}", usage()),
+        ArgsError::Version => println!("{command} {}", env!("CARGO_PKG_VERSION")),
+        _ => {
+            eprintln!("Problem parsing arguments: {err}");
+            eprintln!("Try `hello_cargo {command} --help` for more information.");
+        }
+    }
+    process::exit(err.exit_code());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OPTS: &[Opt] = &[
+        Opt::new(Some('i'), "ignore-case", "Ignore case"),
+        Opt::new(Some('w'), "word", "Whole words"),
+        Opt::new(Some('C'), "context", "Context lines").value("n"),
+        Opt::new(None, "color", "When to color")
+            .value("when")
+            .alias("colour"),
+    ];
+
+    fn parse(args: &[&str]) -> Result<Parsed, ArgsError> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        super::parse(OPTS, &args)
+    }
+
+    // (name as written, value) pairs of the options found.
+    fn options(parsed: &Parsed) -> Vec<(&str, Option<&str>)> {
+        parsed
+            .options
+            .iter()
+            .map(|found| (found.name.as_str(), found.value.as_deref()))
+            .collect()
+    }
+
+    #[test]
+    fn short_long_and_bundled_options() {
+        let parsed =
+            parse(&["-iw", "query", "--context=2", "-C3", "-C", "4", "file", "-"]).unwrap();
+        assert_eq!(
+            options(&parsed),
+            [
+                ("-i", None),
+                ("-w", None),
+                ("--context", Some("2")),
+                ("-C", Some("3")),
+                ("-C", Some("4"))
+            ]
+        );
+        assert_eq!(parsed.positional, ["query", "file", "-"]);
+
+        let parsed = parse(&["-iC1", "--colour", "never"]).unwrap();
+        assert_eq!(
+            options(&parsed),
+            [("-i", None), ("-C", Some("1")), ("--colour", Some("never"))]
+        );
+        assert_eq!(parsed.options[2].opt.long, "color");
+    }
+
+    #[test]
+    fn double_dash_ends_options() {
+        let parsed = parse(&["-i", "--", "-w", "--context"]).unwrap();
+        assert_eq!(options(&parsed), [("-i", None)]);
+        assert_eq!(parsed.positional, ["-w", "--context"]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(parse(&["-x"]), Err(ArgsError::UnknownOption("-x".into())));
+        assert_eq!(parse(&["-ix"]), Err(ArgsError::UnknownOption("-x".into())));
+        assert_eq!(
+            parse(&["--nope=1"]),
+            Err(ArgsError::UnknownOption("--nope".into()))
+        );
+        assert_eq!(parse(&["-C"]), Err(ArgsError::MissingValue("-C".into())));
+        assert_eq!(
+            parse(&["--word=yes"]),
+            Err(ArgsError::UnexpectedValue("--word".into()))
+        );
+        assert_eq!(ArgsError::MissingQuery.exit_code(), 2);
+        assert_eq!(ArgsError::Help.exit_code(), 0);
+    }
+
+    #[test]
+    fn help_text() {
+        assert_eq!(
+            describe(OPTS),
+            "  -i, --ignore-case   Ignore case\n\
+             \x20 -w, --word          Whole words\n\
+             \x20 -C, --context <n>   Context lines\n\
+             \x20     --color <when>  When to color\n"
+        );
+    }
+}
@@ -0,0 +1,116 @@
+// File helpers shared by the commands that rewrite files in place: minigrep's
+// `--in-place` and index, and the guessing game's score table.
+//
+// New contents go to a temporary file next to the original, which is then
+// renamed over it. A rename within a directory is atomic, so readers see
+// either the old file or the new one, never a half-written one. Updates that
+// read the file before rewriting it also need a lock file, or two of them at
+// once would each drop the other's change.
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::process;
+
+/// A file being written next to the one it will replace. Dropping it without
+/// calling `replace` deletes it.
+pub struct TempFile {
+    path: PathBuf,
+    pub writer: BufWriter<File>,
+    replaced: bool,
+}
+
+impl TempFile {
+    /// Creates a temporary file in the same directory as `original`.
+    pub fn next_to(original: &Path) -> io::Result<TempFile> {
+        let name = original.file_name().unwrap_or_default().to_string_lossy();
+        let path = original.with_file_name(format!(".{name}.hello_cargo-{}.tmp", process::id()));
+        let file = File::options().write(true).create_new(true).open(&path)?;
+        Ok(TempFile {
+            path,
+            writer: BufWriter::new(file),
+            replaced: false,
+        })
+    }
+
+    /// Flushes the new contents to disk, gives them the original's permissions
+    /// if it exists, and moves them into its place.
+    pub fn replace(mut self, original: &Path) -> io::Result<()> {
+        self.writer.flush()?;
+        let file = self.writer.get_ref();
+        file.sync_all()?;
+        match fs::metadata(original) {
+            Ok(metadata) => file.set_permissions(metadata.permissions())?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+        fs::rename(&self.path, original)?;
+        self.replaced = true;
+        Ok(())
+    }
+}
+
+impl Drop for TempFile {
+    fn drop(&mut self) {
+        if !self.replaced {
+            let _ = fs::remove_file(&self.path);
+        }
+    }
+}
+
+/// An exclusive lock on `<path>.lock`, held while `path` is read and
+/// rewritten. Dropping it releases the lock.
+///
+/// The lock is the operating system's (`File::lock`), so it also goes away
+/// with a process that dies holding it. The file itself is left in place:
+/// deleting it would let a waiter that already opened it and a newcomer that
+/// creates a fresh one both hold "the" lock.
+pub struct LockFile {
+    _file: File,
+}
+
+impl LockFile {
+    /// Takes the lock on `path`, waiting while another process holds it.
+    pub fn acquire(path: &Path) -> io::Result<LockFile> {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".lock");
+        let file = File::options()
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(PathBuf::from(name))?;
+        file.lock()?;
+        Ok(LockFile { _file: file })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn locks_wait_for_each_other() {
+        let path = env::temp_dir().join(format!("fsutil-lock-{}.txt", process::id()));
+        // A lock file left behind by an earlier holder doesn't get in the way.
+        fs::write(path.with_extension("txt.lock"), "").unwrap();
+        let lock = LockFile::acquire(&path).unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let waiter = {
+            let path = path.clone();
+            thread::spawn(move || {
+                let _lock = LockFile::acquire(&path).unwrap();
+                tx.send(()).unwrap();
+            })
+        };
+        assert!(rx.recv_timeout(Duration::from_millis(100)).is_err());
+        drop(lock);
+        rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        waiter.join().unwrap();
+        fs::remove_file(path.with_extension("txt.lock")).unwrap();
+    }
+}
@@ -1,17 +1,55 @@
+// Chapter demos from The Rust Programming Language, one module per chapter,
+// plus the registry and menu that the `hello_cargo` binary is built on.
+//
+// The reusable types from the chapters are re-exported at the crate root so
+// other crates can use them without knowing which chapter defines them.
+
+pub mod ch1;
+pub mod ch2;
+pub mod ch3;
+pub mod ch4;
+pub mod ch5;
+pub mod ch6;
+pub mod ch7;
+pub mod ch8;
+pub mod ch9;
+pub mod ch10;
+pub mod ch11;
+pub mod ch12;
+pub mod ch13;
+pub mod ch14;
+pub mod ch15;
+pub mod ch16;
+pub mod ch17;
+pub mod chapter;
+pub mod cli;
+pub mod fsutil;
+pub mod menu;
+
+// The guessing game engine and command (ch2).
+pub use ch2::ch2::guess;
+pub use ch2::game::{Difficulty, GuessingGame, Outcome, Settings};
+pub use ch2::net::{Ending, Server};
+pub use ch2::scores::{Score, ScoreBoard};
+pub use ch2::solver::{Benchmark, Solver, worst_case_attempts};
+// Geometry (ch5).
+pub use ch5::ch5_3::Rectangle;
+// IP addresses (ch6).
+pub use ch6::ch6::{IpAddr, IpAddrKind};
+// Validated guesses (ch9).
+pub use ch9::ch9_3::{Guess, OutOfRange};
+// Traits (ch10).
+pub use ch10::ch10_2::{NewsArticle, SocialPost, Summary, notify};
+// The minigrep command line tool (ch12).
+pub use ch12::ch12 as minigrep;
+// Smart pointers and the mock-object example (ch15).
+pub use ch15::ch15::{LimitTracker, Messenger};
+// Concurrency helpers (ch16).
+pub use ch16::ch16::{fan_in, parallel_count};
+
 pub fn add(left: u64, right: u64) -> u64 {
     left + right
 }
-#[derive(Debug)]
-struct Rectangle {
-    width: u32,
-    height: u32,
-}
-
-impl Rectangle {
-    fn can_hold(&self, other: &Rectangle) -> bool {
-        self.width > other.width && self.height > other.height
-    }
-}
 
 #[cfg(test)]
 mod tests {
@@ -23,12 +61,12 @@ mod tests {
         assert_eq!(result, 4);
     }
 
-
     #[test]
-
-    fn anotherfn(){
+    #[should_panic(expected = "make this test failed")]
+    fn anotherfn() {
         panic!("make this test failed");
     }
+
     #[test]
     fn larger_can_hold_smaller() {
         let larger = Rectangle {
@@ -40,23 +78,71 @@ mod tests {
             height: 1,
         };
 
-        assert!(smaller.can_hold(&larger),
-        "Greeting did not contain name, value was "
-    );
+        assert!(
+            larger.can_hold(&smaller),
+            "{larger:?} should be able to hold {smaller:?}"
+        );
     }
 
-
     #[test]
-    fn my_test() -> Result<(),String>{
-        let result= add(2,2);
-        if result==5 {
+    fn my_test() -> Result<(), String> {
+        let result = add(2, 2);
+        if result == 4 {
             Ok(())
-        }
-        else{
-            Err("two tweo plue is 4".to_string())
+        } else {
+            Err(format!("two plus two should be 4, got {result}"))
         }
     }
 
+    #[test]
+    fn ip_addresses() {
+        let home = IpAddr {
+            kind: IpAddrKind::V4,
+            address: String::from("127.0.0.1"),
+        };
+        assert_eq!(home.kind, IpAddrKind::V4);
+        assert_eq!(home.address, "127.0.0.1");
+    }
 
-    
-}
\ No newline at end of file
+    #[test]
+    fn guess_keeps_its_value() {
+        assert_eq!(Guess::new(42).value(), 42);
+    }
+
+    #[test]
+    #[should_panic(expected = "between 1 and 100")]
+    fn guess_rejects_out_of_range() {
+        Guess::new(101);
+    }
+
+    #[test]
+    fn guess_in_a_range_returns_an_error() {
+        assert_eq!(Guess::in_range(-3, -5..=5).unwrap().value(), -3);
+        let err = Guess::in_range(6, -5..=5).err().unwrap();
+        assert_eq!(err, OutOfRange { value: 6, min: -5, max: 5 });
+        assert_eq!(err.to_string(), "Guess value must be between -5 and 5, got 6.");
+    }
+
+    #[test]
+    fn notify_uses_summary() {
+        let post = SocialPost {
+            username: String::from("horse_ebooks"),
+            content: String::from("of course"),
+            reply: false,
+            repost: false,
+        };
+        let mut out = Vec::new();
+        notify(&mut out, &post).unwrap();
+        assert_eq!(out, b"Breaking news! horse_ebooks: of course\n");
+    }
+
+    #[test]
+    fn concurrency_helpers() {
+        assert_eq!(parallel_count(8), 8);
+
+        let rx = fan_in((0..4).map(|i| move |tx: std::sync::mpsc::Sender<i32>| tx.send(i).unwrap()));
+        let mut received: Vec<i32> = rx.iter().collect();
+        received.sort();
+        assert_eq!(received, [0, 1, 2, 3]);
+    }
+}
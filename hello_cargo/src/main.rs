@@ -1,34 +1,98 @@
-// mod ch1;
-// mod ch2;
-// mod ch3;
-// mod ch4;
-// mod ch5;
-// mod ch6;
-// mod ch7;
-mod ch12;
-// use crate::ch1::ch1::ch1;
-// use crate::ch2::ch2::ch2;
-// use crate::ch3::ch3::ch3;
-// // use crate::ch4::ch4_1::ch4_1_demo;
-// // use crate::ch4::ch4_2::ch4_2_demo;
-// use crate::ch4::{ch4_1::ch4_1_demo, ch4_2::ch4_2_demo,ch4_3::ch4_3_demo};
-// use crate::ch5::{ch5_1::ch5_1,ch5_2::ch5_2,ch5_3::ch5_3};
-// use crate::ch6::ch6::ch6;
-// use crate::ch7::ch7::ch7;
-use crate::ch12::ch12::ch12;
+use std::env;
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::process;
+
+use hello_cargo::chapter::{self, Chapter};
+use hello_cargo::{guess, menu, minigrep};
 
 fn main() {
-    
-    // ch1();
-    // ch2();
-    // ch3();
-    // ch4_1_demo();
-    // ch4_2_demo();
-    // ch4_3_demo();
-    // ch5_1();
-    // ch5_2();
-    // ch5_3();
-    // ch6();
-    // ch7();
-    ch12();
+    let args: Vec<String> = env::args().collect();
+
+    match args.get(1).map(String::as_str) {
+        Some("list") => list(args.get(2).map(String::as_str)),
+        Some("run") => match args.get(2).map(String::as_str) {
+            Some("--all") => run_all(),
+            Some(id) => run_one(id),
+            None => {
+                eprintln!("Missing chapter name; try `hello_cargo list`");
+                process::exit(1);
+            }
+        },
+        Some("menu") => {
+            if let Err(e) = menu::run(&mut io::stdout()) {
+                eprintln!("menu failed: {e}");
+                process::exit(1);
+            }
+        }
+        Some("minigrep") => minigrep::ch12(&args[1..]),
+        Some("guess") => guess(&args[1..]),
+        Some("help" | "--help" | "-h") => print_usage(),
+        Some(other) => {
+            eprintln!("Unknown command: {other}");
+            print_usage();
+            process::exit(1);
+        }
+        None => {
+            print_usage();
+            process::exit(1);
+        }
+    }
+}
+
+fn print_usage() {
+    println!("Usage:");
+    println!("  hello_cargo list [topic]                          list chapter demos, optionally by topic");
+    println!("  hello_cargo run <chapter>                         run one chapter, e.g. `hello_cargo run ch6`");
+    println!("  hello_cargo run --all                             run every non-interactive chapter in order");
+    println!("  hello_cargo menu                                  pick chapters from an interactive menu");
+    println!("  hello_cargo minigrep [options] <query> <path>...  the chapter 12 command line tool");
+    println!("  hello_cargo minigrep index <dir>                  index a directory's words for minigrep query");
+    println!("  hello_cargo minigrep query --index <dir> <query>  search an index: words, AND, OR, \"phrases\"");
+    println!("  hello_cargo guess [options]                       play the chapter 2 guessing game");
+    println!("  hello_cargo guess serve [options]                 host a guessing race over TCP");
+    println!("  hello_cargo guess join [options]                  join a guessing race");
+}
+
+fn list(topic: Option<&str>) {
+    let chapters: Vec<&dyn Chapter> = match topic {
+        Some(term) => chapter::search(term).collect(),
+        None => chapter::all().collect(),
+    };
+    for chapter in chapters {
+        println!("{:<6} {:<36} {}", chapter.id(), chapter.title(), chapter.topics().join(", "));
+    }
+}
+
+fn run_chapter(chapter: &dyn Chapter) {
+    let mut stdout = io::stdout();
+    if let Err(e) = chapter.run(&mut stdout) {
+        eprintln!("{} failed: {e}", chapter.id());
+        process::exit(1);
+    }
+}
+
+fn run_one(id: &str) {
+    let Some(chapter) = chapter::find(id) else {
+        eprintln!("Unknown chapter: {id}; try `hello_cargo list`");
+        process::exit(1);
+    };
+    run_chapter(chapter);
+}
+
+fn run_all() {
+    let mut failed = Vec::new();
+    for chapter in chapter::all().filter(|chapter| !chapter.interactive()) {
+        println!("===== {} - {} =====", chapter.id(), chapter.title());
+        // Some chapters (ch9_2) panic on purpose; keep going with the rest.
+        if panic::catch_unwind(AssertUnwindSafe(|| run_chapter(chapter))).is_err() {
+            failed.push(chapter.id());
+        }
+        println!();
+    }
+
+    if !failed.is_empty() {
+        eprintln!("Chapters that panicked: {}", failed.join(", "));
+        process::exit(1);
+    }
 }
@@ -0,0 +1,206 @@
+// Interactive chapter menu behind `hello_cargo menu`.
+//
+// Input is read a line at a time with `io::stdin().read_line`, the same way
+// ch2's guessing game does, so a chapter that reads stdin itself can run from
+// the menu without fighting over a held stdin lock.
+
+use std::io::{self, Write};
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::chapter::{self, Chapter};
+
+/// What the read loop should do after a command.
+#[derive(Debug, PartialEq)]
+pub enum Flow {
+    Continue,
+    Quit,
+}
+
+pub struct Menu {
+    chapters: Vec<&'static dyn Chapter>,
+    // Index of the chapter that ran last, used by `next` and `prev`.
+    current: Option<usize>,
+}
+
+impl Default for Menu {
+    fn default() -> Menu {
+        Menu::new()
+    }
+}
+
+impl Menu {
+    pub fn new() -> Menu {
+        Menu {
+            chapters: chapter::all().collect(),
+            current: None,
+        }
+    }
+
+    pub fn print_menu(&self, out: &mut dyn Write) -> io::Result<()> {
+        writeln!(out, "Chapters:")?;
+        for (i, chapter) in self.chapters.iter().enumerate() {
+            writeln!(out, "{:>4}. {:<6} {}", i + 1, chapter.id(), chapter.title())?;
+        }
+        writeln!(
+            out,
+            "Commands: <number> or <id> to run, next, prev, search <topic>, list, help, quit"
+        )
+    }
+
+    fn prompt(&self, out: &mut dyn Write) -> io::Result<()> {
+        match self.current {
+            Some(i) => write!(out, "[{}] > ", self.chapters[i].id())?,
+            None => write!(out, "> ")?,
+        }
+        out.flush()
+    }
+
+    /// Handles one line of input.
+    pub fn handle(&mut self, line: &str, out: &mut dyn Write) -> io::Result<Flow> {
+        let line = line.trim();
+        let (command, rest) = match line.split_once(char::is_whitespace) {
+            Some((command, rest)) => (command, rest.trim()),
+            None => (line, ""),
+        };
+
+        match command {
+            "" => {}
+            "quit" | "q" | "exit" => return Ok(Flow::Quit),
+            "list" | "menu" => self.print_menu(out)?,
+            "help" | "?" => {
+                writeln!(out, "  <number> | <id>  run a chapter, e.g. `3` or `ch6`")?;
+                writeln!(out, "  next / prev      run the chapter after / before the last one")?;
+                writeln!(out, "  search <topic>   list chapters about a topic")?;
+                writeln!(out, "  list             show the menu again")?;
+                writeln!(out, "  quit             leave the menu")?;
+            }
+            "next" => match self.current {
+                None => self.run(0, out)?,
+                Some(i) if i + 1 < self.chapters.len() => self.run(i + 1, out)?,
+                Some(_) => writeln!(out, "Already at the last chapter.")?,
+            },
+            "prev" => match self.current {
+                Some(i) if i > 0 => self.run(i - 1, out)?,
+                _ => writeln!(out, "Already at the first chapter.")?,
+            },
+            "search" if rest.is_empty() => writeln!(out, "Usage: search <topic>")?,
+            "search" => self.search(rest, out)?,
+            _ => match self.lookup(command) {
+                Some(i) => self.run(i, out)?,
+                None => writeln!(out, "Unknown command `{line}`; type `help` for commands.")?,
+            },
+        }
+        Ok(Flow::Continue)
+    }
+
+    // Accepts a 1-based menu number or a chapter id.
+    fn lookup(&self, input: &str) -> Option<usize> {
+        match input.parse::<usize>() {
+            Ok(n) if n >= 1 && n <= self.chapters.len() => Some(n - 1),
+            Ok(_) => None,
+            Err(_) => self.chapters.iter().position(|chapter| chapter.id() == input),
+        }
+    }
+
+    fn search(&self, term: &str, out: &mut dyn Write) -> io::Result<()> {
+        let mut found = false;
+        for chapter in chapter::search(term) {
+            if let Some(i) = self.lookup(chapter.id()) {
+                writeln!(out, "{:>4}. {:<6} {}", i + 1, chapter.id(), chapter.title())?;
+                found = true;
+            }
+        }
+        if !found {
+            writeln!(out, "No chapters mention `{term}`.")?;
+        }
+        Ok(())
+    }
+
+    fn run(&mut self, index: usize, out: &mut dyn Write) -> io::Result<()> {
+        let chapter = self.chapters[index];
+        self.current = Some(index);
+        writeln!(out, "===== {} - {} =====", chapter.id(), chapter.title())?;
+        // A panicking chapter (ch9_2 does on purpose) shouldn't end the session.
+        match panic::catch_unwind(AssertUnwindSafe(|| chapter.run(out))) {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => writeln!(out, "{} failed: {e}", chapter.id())?,
+            Err(_) => writeln!(out, "{} panicked.", chapter.id())?,
+        }
+        writeln!(out)
+    }
+}
+
+/// Runs the menu until `quit` or end of input.
+pub fn run(out: &mut dyn Write) -> io::Result<()> {
+    let mut menu = Menu::new();
+    menu.print_menu(out)?;
+
+    loop {
+        menu.prompt(out)?;
+
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line)? == 0 {
+            // End of input (Ctrl-D or a closed pipe).
+            writeln!(out)?;
+            return Ok(());
+        }
+
+        if menu.handle(&line, out)? == Flow::Quit {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn handle(menu: &mut Menu, line: &str) -> (Flow, String) {
+        let mut out = Vec::new();
+        let flow = menu.handle(line, &mut out).unwrap();
+        (flow, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn runs_chapter_by_number_and_id() {
+        let mut menu = Menu::new();
+        let (_, output) = handle(&mut menu, "1\n");
+        assert!(output.contains("Hello, world!"));
+
+        let (_, output) = handle(&mut menu, "ch4_3");
+        assert!(output.contains("first word: hello"));
+    }
+
+    #[test]
+    fn next_and_prev_walk_the_registry() {
+        let mut menu = Menu::new();
+        let (_, output) = handle(&mut menu, "next");
+        assert!(output.contains("===== ch1"));
+
+        let (_, output) = handle(&mut menu, "prev");
+        assert!(output.contains("Already at the first chapter."));
+
+        handle(&mut menu, "ch3");
+        let (_, output) = handle(&mut menu, "next");
+        assert!(output.contains("===== ch4_1"));
+    }
+
+    #[test]
+    fn search_lists_matching_chapters() {
+        let mut menu = Menu::new();
+        let (_, output) = handle(&mut menu, "search borrowing");
+        assert!(output.contains("ch4_2"));
+        assert!(!output.contains("ch1 "));
+    }
+
+    #[test]
+    fn bad_input_keeps_the_menu_running() {
+        let mut menu = Menu::new();
+        for line in ["0", "999", "ch99", "dance", "search"] {
+            let (flow, output) = handle(&mut menu, line);
+            assert_eq!(flow, Flow::Continue);
+            assert!(!output.is_empty(), "no feedback for {line:?}");
+        }
+        assert_eq!(handle(&mut menu, "quit").0, Flow::Quit);
+    }
+}
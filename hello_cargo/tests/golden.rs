@@ -0,0 +1,68 @@
+// Golden-file tests: run each deterministic chapter through the binary and
+// compare what it writes with the snapshot in tests/golden/<id>.txt.
+//
+// After an intentional change to a demo, regenerate the snapshots with:
+//     UPDATE_GOLDEN=1 cargo test --test golden
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+fn golden_path(id: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("golden")
+        .join(format!("{id}.txt"))
+}
+
+fn run_chapter(id: &str) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_hello_cargo"))
+        .args(["run", id])
+        .output()
+        .expect("failed to start hello_cargo");
+    assert!(
+        output.status.success(),
+        "`hello_cargo run {id}` failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout).expect("chapter output should be UTF-8")
+}
+
+fn check(id: &str) {
+    let actual = run_chapter(id);
+    let path = golden_path(id);
+
+    if env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, &actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|_| {
+        panic!("missing snapshot {}; run with UPDATE_GOLDEN=1", path.display())
+    });
+    assert_eq!(
+        actual, expected,
+        "output of {id} changed; rerun with UPDATE_GOLDEN=1 if that was intended"
+    );
+}
+
+// Only chapters whose output doesn't depend on stdin, thread timing, HashMap
+// iteration order or files in the working directory (ch2, ch8_3, ch9_2 and
+// ch16 are left out).
+macro_rules! golden_tests {
+    ($($id:ident),* $(,)?) => {
+        $(
+            #[test]
+            fn $id() {
+                check(stringify!($id));
+            }
+        )*
+    };
+}
+
+golden_tests!(
+    ch1, ch3, ch4_1, ch4_2, ch4_3, ch5_1, ch5_2, ch5_3, ch6, ch7, ch8_1, ch8_2, ch9_1, ch9_3,
+    ch10_1, ch10_2, ch10_3, ch11_1, ch11_2, ch11_3, ch12, ch13, ch14, ch15, ch17,
+);
@@ -0,0 +1,234 @@
+// End-to-end tests for `hello_cargo guess`: the game played over stdin.
+
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+
+use hello_cargo::{Difficulty, GuessingGame};
+
+fn command(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_hello_cargo"));
+    command.arg("guess").args(args);
+    // Never touch the real high scores.
+    command.env("GUESS_SCORES", std::env::temp_dir().join("guess-e2e-unused.tsv"));
+    command
+}
+
+fn scratch(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("guess-e2e-{name}-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn guess(args: &[&str], input: &str) -> Output {
+    let mut child = command(&[])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to start hello_cargo");
+    child.stdin.take().unwrap().write_all(input.as_bytes()).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn seeded_game_is_won() {
+    let secret = GuessingGame::from_seed(Difficulty::Easy.settings(), 5).secret();
+    let output = guess(&["-d", "easy", "--seed", "5"], &format!("0\n{secret}\n"));
+    assert_eq!(output.status.code(), Some(0));
+    let text = stdout(&output);
+    assert!(text.starts_with("Guess the number between 1 and 50! You have 10 guesses.\n"), "{text}");
+    assert!(text.contains("Your guess must be between 1 and 50.\n"));
+    assert!(text.ends_with("You win, in 1 guess!\n"));
+}
+
+#[test]
+fn running_out_of_guesses_loses() {
+    let output = guess(&["--min", "1", "--max", "2", "-a", "1", "--seed", "5"], "3\nnope\n");
+    assert_eq!(output.status.code(), Some(1));
+
+    let secret = GuessingGame::from_seed(Difficulty::Normal.settings(), 5).secret();
+    let wrong = if secret == 1 { 2 } else { 1 };
+    let output = guess(&["-a", "1", "--seed", "5"], &format!("{wrong}\n{secret}\n"));
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).ends_with(&format!("Out of guesses! The number was {secret}.\n")));
+}
+
+#[test]
+fn bad_options_exit_2() {
+    let output = guess(&["--min", "9", "--max", "3"], "");
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Try `hello_cargo guess --help`"));
+
+    let output = guess(&["--help"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).starts_with("Usage: hello_cargo guess [options]"));
+}
+
+#[test]
+fn the_computer_plays() {
+    let output = guess(&["--solve", "-d", "hard", "--seed", "5"], "");
+    assert_eq!(output.status.code(), Some(0));
+    let text = stdout(&output);
+    assert!(text.starts_with("Guess the number between 1 and 1000! You have 10 guesses.\n\
+                              Halving the range takes at most 10 guesses.\n\
+                              Guessing 500: "), "{text}");
+    let secret = GuessingGame::from_seed(Difficulty::Hard.settings(), 5).secret();
+    assert!(text.contains(&format!("Guessing {secret}: that's it, in ")), "{text}");
+
+    // The midpoint of a range below zero rounds down too.
+    let output = guess(&["--solve", "--min", "-10", "--max", "-1", "--seed", "3"], "");
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("at most 4 guesses.\nGuessing -6: "), "{}", stdout(&output));
+
+    let output = guess(&["--bench", "200", "--max", "1000"], "");
+    assert_eq!(output.status.code(), Some(0));
+    let text = stdout(&output);
+    assert!(text.contains("Played 200 games, seeded with 0 onwards.\nguesses  games\n"), "{text}");
+    // Normal gives 7 guesses, fewer than halving 1 to 1000 can promise.
+    assert!(!text.contains("   lost      0 "), "{text}");
+    assert!(text.contains("\n      7  ") && !text.contains("\n      8  "), "{text}");
+}
+// Wins an unseeded game by halving the range after every answer.
+fn win_by_bisection(scores: &Path, args: &[&str]) -> String {
+    let mut child = command(args)
+        .env("GUESS_SCORES", scores)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start hello_cargo");
+    let mut stdin = child.stdin.take().unwrap();
+    let lines = BufReader::new(child.stdout.take().unwrap()).lines();
+    let (mut low, mut high) = (1, 50);
+    let mut guess = 0;
+    let mut transcript = String::new();
+    for line in lines {
+        let line = line.unwrap();
+        transcript.push_str(&line);
+        transcript.push('\n');
+        match line.as_str() {
+            "Please input your guess." => {
+                guess = (low + high) / 2;
+                writeln!(stdin, "{guess}").unwrap();
+            }
+            "Too small!" => low = guess + 1,
+            "Too big!" => high = guess - 1,
+            _ => {}
+        }
+    }
+    assert_eq!(child.wait().unwrap().code(), Some(0), "{transcript}");
+    transcript
+}
+
+#[test]
+fn wins_are_recorded_as_high_scores() {
+    let dir = scratch("record");
+    let scores = dir.join("data/scores.tsv");
+    let transcript = win_by_bisection(&scores, &["-d", "easy", "--name", "ann"]);
+    assert!(transcript.ends_with("New personal best at easy!\n"), "{transcript}");
+
+    let text = fs::read_to_string(&scores).unwrap();
+    assert_eq!(text.lines().count(), 2, "{text}");
+    assert!(text.lines().nth(1).unwrap().contains("\teasy\t"));
+    assert!(text.ends_with("\tann\n"));
+
+    let output = command(&["--scores", "-n", "ann"]).env("GUESS_SCORES", &scores).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let table = stdout(&output);
+    assert!(table.starts_with("easy:\n   1. ann  "), "{table}");
+    assert!(table.contains("Personal bests for ann:\n  easy     "), "{table}");
+
+    // Seeded games, whose secret can be known in advance, aren't recorded.
+    let secret = GuessingGame::from_seed(Difficulty::Easy.settings(), 5).secret();
+    let output = command(&["-d", "easy", "--seed", "5"])
+        .env("GUESS_SCORES", &scores)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .and_then(|mut child| {
+            writeln!(child.stdin.take().unwrap(), "{secret}")?;
+            child.wait_with_output()
+        })
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(fs::read_to_string(&scores).unwrap(), text);
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn damaged_scores_are_recovered() {
+    let dir = scratch("damaged");
+    let scores = dir.join("scores.tsv");
+    fs::write(&scores, "# hello_cargo guess scores 1\n1792108800\thard\t8\t61000\tbea\n\0\0\0\n").unwrap();
+
+    let output = command(&["--scores", "--name", "bea"]).env("GUESS_SCORES", &scores).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "hard:\n   1. bea   8 guesses    61.0s  2026-10-16\n\
+         Personal bests for bea:\n  easy    -\n  normal  -\n  hard     8 guesses    61.0s  2026-10-16\n"
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("skipped 1 unreadable line"));
+    assert!(!dir.join("scores.tsv.corrupt").exists());
+
+    // The next win copies the damaged file aside, then rewrites it without the damage.
+    win_by_bisection(&scores, &["-d", "easy", "--name", "bea"]);
+    assert!(fs::read(dir.join("scores.tsv.corrupt")).unwrap().ends_with(b"\0\0\0\n"));
+    let text = fs::read_to_string(&scores).unwrap();
+    assert_eq!(text.lines().count(), 3, "{text}");
+    assert!(!text.contains('\0'));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn players_race_over_tcp() {
+    let mut server = command(&["serve", "--listen", "127.0.0.1:0", "--players", "2", "--seed", "5"])
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start the server");
+    let mut log = BufReader::new(server.stdout.take().unwrap()).lines();
+    let banner = log.next().unwrap().unwrap();
+    let addr = banner
+        .split_once(" on ")
+        .and_then(|(_, rest)| rest.split_once(';'))
+        .map(|(addr, _)| addr.to_string())
+        .unwrap_or_else(|| panic!("no address in {banner:?}"));
+    assert!(banner.starts_with("Serving a race between 1 and 100 on 127.0.0.1:"), "{banner}");
+
+    let join = |name: &str| {
+        command(&["join", "--connect", &addr, "--name", name])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .expect("failed to start a player")
+    };
+    let ann = join("ann");
+    assert_eq!(log.next().unwrap().unwrap(), "ann joined (1 of 2).");
+    let mut bob = join("bob");
+    assert_eq!(log.next().unwrap().unwrap(), "bob joined (2 of 2).");
+    assert_eq!(log.next().unwrap().unwrap(), "The race is on!");
+
+    let secret = GuessingGame::from_seed(Difficulty::Normal.settings(), 5).secret();
+    writeln!(bob.stdin.take().unwrap(), "{secret}").unwrap();
+    assert_eq!(server.wait().unwrap().code(), Some(0));
+    let rest: Vec<String> = log.map(Result::unwrap).collect();
+    assert_eq!(rest, [format!("bob won, in 1 guess. The number was {secret}.")]);
+
+    let finish = |mut player: std::process::Child| {
+        let mut out = String::new();
+        player.stdout.take().unwrap().read_to_string(&mut out).unwrap();
+        (player.wait().unwrap().code(), out)
+    };
+    let (code, out) = finish(bob);
+    assert_eq!(code, Some(0));
+    assert!(out.ends_with("The race is on! Please input your guess.\nYou win, in 1 guess!\n"), "{out}");
+    let (code, out) = finish(ann);
+    assert_eq!(code, Some(1));
+    assert!(out.ends_with(&format!("bob won, in 1 guess. The number was {secret}.\n")), "{out}");
+}
@@ -0,0 +1,429 @@
+// End-to-end tests for `hello_cargo minigrep`: output and grep-style exit codes.
+
+use std::env;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+const POEM: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src/ch12/poem.txt");
+
+fn command(args: &[&str]) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_hello_cargo"));
+    // Keep the tests independent of the environment and any real config file.
+    command
+        .arg("minigrep")
+        .args(args)
+        .env_remove("IGNORE_CASE")
+        .env_remove("MINIGREP_COLOR")
+        .env("MINIGREP_CONFIG", "");
+    command
+}
+
+fn minigrep(args: &[&str]) -> Output {
+    command(args).output().expect("failed to start hello_cargo")
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn prints_matching_lines_with_numbers() {
+    let output = minigrep(&["frog", POEM]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "7:How public, like a frog\n");
+}
+
+#[test]
+fn ignore_case_from_env_or_flag() {
+    assert_eq!(minigrep(&["HOW", POEM]).status.code(), Some(1));
+    assert_eq!(
+        stdout(&minigrep(&["-i", "HOW", POEM])),
+        "6:How dreary to be somebody!\n7:How public, like a frog\n"
+    );
+
+    let output = command(&["HOW", POEM])
+        .env("IGNORE_CASE", "1")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+
+    let output = command(&["-s", "HOW", POEM])
+        .env("IGNORE_CASE", "1")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(1));
+}
+
+#[test]
+fn searches_directories_with_file_name_prefixes() {
+    let output = command(&[
+        "--include=*.rs",
+        "--exclude",
+        "ch8_3.rs",
+        "-E",
+        "^pub fn ch8",
+        "src",
+    ])
+    .current_dir(env!("CARGO_MANIFEST_DIR"))
+    .output()
+    .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "src/ch8/ch8_1.rs:5:pub fn ch8_1(out: &mut dyn Write) -> io::Result<()> {\n\
+         src/ch8/ch8_2.rs:5:pub fn ch8_2(out: &mut dyn Write) -> io::Result<()> {\n"
+    );
+}
+
+#[test]
+fn parallel_search_matches_sequential_output() {
+    let search = |jobs: &str| {
+        command(&["-j", jobs, "-C", "1", "--color=always", "-w", "Write", "src"])
+            .current_dir(env!("CARGO_MANIFEST_DIR"))
+            .output()
+            .unwrap()
+    };
+    let sequential = search("1");
+    assert_eq!(sequential.status.code(), Some(0));
+    assert!(stdout(&sequential).lines().count() > 100);
+    for jobs in ["2", "8"] {
+        let parallel = search(jobs);
+        assert_eq!(parallel.status, sequential.status);
+        assert_eq!(stdout(&parallel), stdout(&sequential), "-j {jobs}");
+    }
+}
+
+#[test]
+fn json_records_and_summary() {
+    let output = minigrep(&["--json", "-E", "fr?o", POEM]);
+    assert_eq!(output.status.code(), Some(0));
+    let text = stdout(&output);
+    let lines: Vec<&str> = text.lines().collect();
+    assert_eq!(lines.len(), 2, "{text}");
+    assert_eq!(
+        lines[0],
+        format!(
+            r#"{{"type":"match","path":"{POEM}","line_number":7,"offset":142,"line":"How public, like a frog","#,
+        ) + r#""submatches":[{"start":19,"end":22,"text":"fro"}]}"#
+    );
+    assert!(
+        lines[1].starts_with(
+            r#"{"type":"summary","files_searched":1,"files_matched":1,"matched_lines":1,"elapsed_ms":"#
+        ),
+        "{}",
+        lines[1]
+    );
+
+    let output = minigrep(&["--json", "toad", POEM]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(stdout(&output).starts_with(r#"{"type":"summary","files_searched":1,"files_matched":0,"#));
+}
+
+#[test]
+fn counts_file_lists_invert_and_max_count() {
+    assert_eq!(stdout(&minigrep(&["-c", "How", POEM])), "2\n");
+    assert_eq!(
+        stdout(&minigrep(&["--count", "-v", "o", POEM, POEM])),
+        format!("{POEM}:1\n{POEM}:1\n")
+    );
+
+    let output = minigrep(&["-l", "frog", POEM, "-"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), format!("{POEM}\n"));
+
+    let output = minigrep_with_stdin(&["-L", "frog", POEM, "-"], b"toad\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "(standard input)\n");
+    assert_eq!(minigrep(&["-L", "frog", POEM]).status.code(), Some(1));
+
+    assert_eq!(stdout(&minigrep(&["-v", "o", POEM])), "5:\n");
+    assert_eq!(
+        stdout(&minigrep(&["-m", "1", "-A", "1", "How", POEM])),
+        "6:How dreary to be somebody!\n7-How public, like a frog\n"
+    );
+    assert_eq!(minigrep(&["-m", "0", "How", POEM]).status.code(), Some(1));
+}
+
+#[test]
+fn config_file_layers_under_env_and_flags() {
+    let config = env::temp_dir().join(format!("minigrep-config-{}.toml", std::process::id()));
+    fs::write(&config, "ignore-case = true\ncolor = \"always\"\nmax-count = 1\n").unwrap();
+    let search = |args: &[&str], color_env: Option<&str>| {
+        let mut command = command(args);
+        command.env("MINIGREP_CONFIG", &config);
+        if let Some(color) = color_env {
+            command.env("MINIGREP_COLOR", color);
+        }
+        command.output().unwrap()
+    };
+
+    let output = search(&["HOW", POEM], Some("never"));
+    assert_eq!(stdout(&output), "6:How dreary to be somebody!\n");
+    let output = search(&["-m", "5", "-s", "How", POEM], None);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).contains("\x1b[1;31mHow\x1b[0m public"));
+
+    let output = search(&["--print-config", "-m", "5"], Some("never"));
+    assert_eq!(output.status.code(), Some(0));
+    let settings = stdout(&output);
+    let line = |setting: &str| {
+        settings
+            .lines()
+            .find(|line| line.starts_with(&format!("{setting} = ")))
+            .unwrap()
+            .to_string()
+    };
+    assert!(line("ignore-case").ends_with(&format!("# config file {}", config.display())));
+    assert!(line("color").ends_with("# environment variable MINIGREP_COLOR"));
+    assert!(line("max-count").ends_with("# command line -m"));
+    assert!(line("mode").ends_with("# default"));
+
+    fs::write(&config, "colour = \"never\"\nbogus = 1\n").unwrap();
+    let output = search(&["frog", POEM], None);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains(".toml:2: unknown setting `bogus`")
+    );
+    fs::remove_file(&config).unwrap();
+}
+
+#[test]
+fn replace_in_place_and_dry_run() {
+    assert_eq!(
+        stdout(&minigrep(&["-E", r"like a (\w+)", "--replace", "unlike a big $1", POEM])),
+        "7:How public, unlike a big frog\n"
+    );
+
+    let path = env::temp_dir().join(format!("minigrep-replace-{}.txt", std::process::id()));
+    fs::write(&path, "a frog\na toad\nfrog, frog\n").unwrap();
+    let file = path.to_str().unwrap();
+
+    let output = minigrep(&["frog", "-r", "newt", "--dry-run", file]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        format!("--- {file}\n+++ {file}\n@@ -1 +1 @@\n-a frog\n+a newt\n@@ -3 +3 @@\n-frog, frog\n+newt, newt\n")
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a frog\na toad\nfrog, frog\n");
+
+    let output = minigrep(&["frog", "-r", "newt", "--in-place", file]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stdout.is_empty());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "a newt\na toad\nnewt, newt\n");
+    assert_eq!(minigrep(&["frog", "-r", "newt", "--in-place", file]).status.code(), Some(1));
+    fs::remove_file(&path).unwrap();
+
+    let output = minigrep(&["--in-place", "frog", POEM]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--in-place needs --replace"));
+}
+
+#[test]
+fn fuzzy_matches_are_ranked() {
+    // "To an admiring bog!" has "To a" together, so it ranks first.
+    let output = minigrep(&["--fuzzy", "toad", POEM]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        "9:To an admiring bog!\n8:To tell your name the livelong day\n"
+    );
+    assert_eq!(
+        stdout(&minigrep(&["--fuzzy", "--color=always", "hpub", POEM])),
+        "\x1b[32m7\x1b[0m\x1b[36m:\x1b[0m\x1b[1;31mH\x1b[0mow \x1b[1;31mpub\x1b[0mlic, like a frog\n"
+    );
+    assert_eq!(stdout(&minigrep(&["--fuzzy", "-c", "toad", POEM])), "2\n");
+    assert_eq!(minigrep(&["--fuzzy", "Toad", POEM]).status.code(), Some(0));
+    assert_eq!(minigrep(&["--fuzzy", "TOAD", POEM]).status.code(), Some(1));
+
+    let output = minigrep(&["--fuzzy", "-r", "x", "toad", POEM]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--replace can't be used with --fuzzy"));
+}
+
+#[test]
+fn index_and_query() {
+    let dir = env::temp_dir().join(format!("minigrep-index-e2e-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(dir.join("sub")).unwrap();
+    fs::copy(POEM, dir.join("poem.txt")).unwrap();
+    fs::write(dir.join("sub/frogs.txt"), "a frog\nlike a public frog\n").unwrap();
+    let root = dir.to_str().unwrap();
+
+    let output = minigrep(&["index", root]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(stdout(&output).starts_with("indexed 2 files (2 read)"));
+
+    let output = minigrep(&["query", "--index", root, "\"a frog\"", "OR", "bog"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        format!(
+            "{root}/poem.txt:7:How public, like a frog\n\
+             {root}/poem.txt:9:To an admiring bog!\n\
+             {root}/sub/frogs.txt:1:a frog\n"
+        )
+    );
+    assert_eq!(minigrep(&["query", "--index", root, "frog toad"]).status.code(), Some(1));
+
+    // A changed file is read again rather than answered from stale postings.
+    fs::write(dir.join("sub/frogs.txt"), "a toad\n").unwrap();
+    let file = fs::File::options().write(true).open(dir.join("sub/frogs.txt")).unwrap();
+    file.set_modified(std::time::UNIX_EPOCH).unwrap();
+    assert_eq!(
+        stdout(&minigrep(&["query", "--index", root, "toad"])),
+        format!("{root}/sub/frogs.txt:1:a toad\n")
+    );
+    assert!(stdout(&minigrep(&["index", root])).starts_with("indexed 2 files (1 read)"));
+
+    let output = minigrep(&["query", "--index", root, "frog", "OR"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("need a word on each side"));
+    fs::remove_dir_all(&dir).unwrap();
+
+    let output = minigrep(&["query", "--index", root, "frog"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Application error"));
+}
+
+#[test]
+fn several_files_get_prefixes_and_missing_ones_are_reported() {
+    let output = command(&["frog", POEM, "no/such/file.txt", POEM])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        stdout(&output),
+        format!("{POEM}:7:How public, like a frog\n").repeat(2)
+    );
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no/such/file.txt"));
+}
+
+#[test]
+fn context_and_color() {
+    let output = minigrep(&["-B1", "frog", POEM]);
+    assert_eq!(
+        stdout(&output),
+        "6-How dreary to be somebody!\n7:How public, like a frog\n"
+    );
+
+    // stdout is a pipe here, so `auto` means no color.
+    let output = minigrep(&["--color=always", "frog", POEM]);
+    assert_eq!(
+        stdout(&output),
+        "\x1b[32m7\x1b[0m\x1b[36m:\x1b[0mHow public, like a \x1b[1;31mfrog\x1b[0m\n"
+    );
+    let output = minigrep(&["--color", "auto", "frog", POEM]);
+    assert_eq!(stdout(&output), "7:How public, like a frog\n");
+}
+
+fn minigrep_with_stdin(args: &[&str], input: &[u8]) -> Output {
+    let mut child = command(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to start hello_cargo");
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn reads_stdin_without_a_path_or_for_dash() {
+    let input = b"one fish\ntwo fish\nred herring\n";
+    assert_eq!(
+        stdout(&minigrep_with_stdin(&["fish"], input)),
+        "1:one fish\n2:two fish\n"
+    );
+    assert_eq!(
+        stdout(&minigrep_with_stdin(&["herring", "-", POEM], input)),
+        "(standard input):3:red herring\n"
+    );
+}
+
+#[test]
+fn non_utf8_and_binary_input() {
+    let output = minigrep_with_stdin(&["frog"], b"caf\xe9 frog\r\nplain\n");
+    assert_eq!(stdout(&output), "1:caf\u{FFFD} frog\n");
+
+    let output = minigrep_with_stdin(&["frog"], b"ELF\0\x01\x02 frog\nmore frog\n");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(stdout(&output), "Binary file (standard input) matches\n");
+    let output = minigrep_with_stdin(&["-c", "frog"], b"ELF\0\x01\x02 frog\nmore frog\n");
+    assert_eq!(stdout(&output), "2\n");
+
+    let path = env::temp_dir().join(format!("minigrep-binary-{}.bin", std::process::id()));
+    fs::write(&path, b"\0\0\0toad\n").unwrap();
+    let output = minigrep(&["toad", path.to_str().unwrap(), POEM]);
+    fs::remove_file(&path).unwrap();
+    assert_eq!(
+        stdout(&output),
+        format!("Binary file {} matches\n", path.display())
+    );
+}
+
+#[test]
+fn characters_that_fold_to_several_match_once() {
+    // `ß` folds to `ss`, so `s` matches it twice over.
+    let output = minigrep_with_stdin(&["-i", "--color", "always", "s"], "Straße\n".as_bytes());
+    assert_eq!(
+        stdout(&output),
+        "\x1b[32m1\x1b[0m\x1b[36m:\x1b[0m\x1b[1;31mS\x1b[0mtra\x1b[1;31mß\x1b[0me\n"
+    );
+    let output = minigrep_with_stdin(&["-i", "-r", "x", "s"], "Straße\n".as_bytes());
+    assert_eq!(stdout(&output), "1:xtraxe\n");
+    let output = minigrep_with_stdin(&["-i", "--json", "s"], "Straße\n".as_bytes());
+    assert!(
+        stdout(&output).contains(
+            r#""submatches":[{"start":0,"end":1,"text":"S"},{"start":4,"end":6,"text":"ß"}]}"#
+        ),
+        "{}",
+        stdout(&output)
+    );
+}
+
+#[test]
+fn exits_1_when_nothing_matches() {
+    let output = minigrep(&["monomorphization", POEM]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(stdout(&output), "");
+}
+
+#[test]
+fn exits_2_on_errors() {
+    let output = minigrep(&["frog", "no/such/file.txt"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Application error"));
+
+    // Without a path the query is searched for in stdin, but a query is required.
+    let output = minigrep(&[]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("not enough arguments"));
+    let output = minigrep(&["--frobnicate", "frog", POEM]);
+    assert_eq!(output.status.code(), Some(2));
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Problem parsing arguments: unknown option `--frobnicate`\n\
+         Try `hello_cargo minigrep --help` for more information.\n"
+    );
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn help_and_version() {
+    let output = minigrep(&["--help"]);
+    assert_eq!(output.status.code(), Some(0));
+    let help = stdout(&output);
+    assert!(help.starts_with("Usage: hello_cargo minigrep [options]"), "{help}");
+    assert!(help.contains("  -A, --after-context <n>"), "{help}");
+
+    let output = minigrep(&["-V"]);
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        stdout(&output),
+        format!("minigrep {}\n", env!("CARGO_PKG_VERSION"))
+    );
+
+    // After `--`, option-like arguments are the query and paths.
+    let output = minigrep_with_stdin(&["--", "-v"], b"keep -v\ndrop\n");
+    assert_eq!(stdout(&output), "1:keep -v\n");
+}